@@ -85,3 +85,41 @@ pub fn args_are_empty<T>(args: &[T]) -> Result<(), CommandErr> {
         ))
     }
 }
+
+/// Convenience function to remove a flag from the arguments of a command, giving whether or not
+/// the flag was present and the remaining arguments.
+#[must_use]
+pub fn take_flag(args: &[String], flag: &str) -> (bool, Vec<String>) {
+    let remaining = args
+        .iter()
+        .filter(|arg| *arg != flag)
+        .cloned()
+        .collect::<Vec<_>>();
+    (remaining.len() != args.len(), remaining)
+}
+
+/// Convenience function to remove an option taking a value from the arguments of a command,
+/// giving the value if the option was present and the remaining arguments.
+///
+/// # Errors
+/// If the option is given without a value.
+pub fn take_option(
+    args: &[String],
+    option: &str,
+) -> Result<(Option<String>, Vec<String>), CommandErr> {
+    let Some(index) = args.iter().position(|arg| arg == option) else {
+        return Ok((None, args.to_vec()));
+    };
+
+    let Some(value) = args.get(index + 1) else {
+        return Err(CommandErr::Usage(format!("{option} requires a value")));
+    };
+
+    let remaining = args[..index]
+        .iter()
+        .chain(&args[index + 2..])
+        .cloned()
+        .collect();
+
+    Ok((Some(value.clone()), remaining))
+}
@@ -2,6 +2,10 @@ use bookmark_storage::{ContentString, Field, ListField, Storeable};
 
 const TAGS_TOKEN: &str = "<tags>";
 const INFO_TOKEN: &str = "<info>";
+const NOTE_TOKEN: &str = "<note>";
+const LINKS_TOKEN: &str = "<links>";
+const VISITS_TOKEN: &str = "<visits>";
+const PINNED_TOKEN: &str = "<pinned>";
 
 #[derive(Debug, Storeable, Default)]
 struct Test {
@@ -18,6 +22,115 @@ struct Test {
     tags: ListField,
 }
 
+#[derive(Debug, Storeable)]
+struct Extended {
+    #[line]
+    ln: ContentString,
+
+    #[string]
+    #[title]
+    #[token(INFO_TOKEN)]
+    info: Field,
+
+    #[string]
+    #[optional]
+    #[token(NOTE_TOKEN)]
+    note: Option<Field>,
+
+    #[composite(link)]
+    #[optional]
+    #[token(LINKS_TOKEN)]
+    links: ListField,
+
+    #[typed]
+    #[default_value(0)]
+    #[token(VISITS_TOKEN)]
+    visits: u64,
+
+    #[typed]
+    #[optional]
+    #[token(PINNED_TOKEN)]
+    pinned: Option<bool>,
+}
+
 fn main() {
     println!("Hello, world!");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bookmark_storage::{ParseErr, Property};
+
+    #[test]
+    pub fn absent_optional_fields() {
+        let old = Extended::from_string("<info> old line", None).expect("line should parse");
+        assert_eq!(old.info(), "old line");
+        assert_eq!(old.note(), None);
+        assert_eq!(old.links().count(), 0);
+        assert_eq!(old.visits(), 0);
+        assert_eq!(old.pinned(), None);
+        assert_eq!(old.to_line(), "<info> old line <visits> 0");
+
+        let line = "<info> a <note> b <links> c <,> d <visits> 4 <pinned> true";
+        let full = Extended::from_string(line, None).expect("line should parse");
+        assert_eq!(full.note(), Some("b"));
+        assert_eq!(full.links().collect::<Vec<_>>(), ["c", "d"]);
+        assert_eq!(full.visits(), 4);
+        assert_eq!(full.pinned(), Some(true));
+        assert_eq!(full.to_line(), line);
+
+        assert!(Test::from_string("<info> a <tags> b", None).is_ok());
+    }
+
+    #[test]
+    pub fn invalid_lines() {
+        assert!(matches!(
+            Extended::from_string("<info> a <visits> many", None),
+            Err(ParseErr::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            Extended::from_string("<info> a <pinned> true <note> b", None),
+            Err(ParseErr::UnexpectedToken { .. })
+        ));
+        assert!(matches!(
+            Extended::from_string("<note> b", None),
+            Err(ParseErr::UnexpectedToken { .. })
+        ));
+        assert!(matches!(
+            Test::from_string("<info> a", None),
+            Err(ParseErr::MissingToken { .. })
+        ));
+    }
+
+    #[test]
+    pub fn set_optional_and_typed() {
+        let mut value = Extended::from_string("<info> a", None).expect("line should parse");
+        assert!(!value.is_edited());
+
+        value.set_visits(2);
+        assert!(value.is_edited());
+        value
+            .set("pinned", Property::Single("false".into()))
+            .expect("pinned should be settable");
+        value
+            .set("note", Property::Single("b".into()))
+            .expect("note should be settable");
+        assert!(value.set("visits", Property::Single("-1".into())).is_err());
+
+        let parsed = Extended::from_string(value.to_line(), None).expect("line should parse");
+        assert_eq!(parsed.visits(), 2);
+        assert_eq!(parsed.pinned(), Some(false));
+        assert_eq!(parsed.note(), Some("b"));
+
+        value
+            .set("note", Property::Single(String::new()))
+            .expect("note should be clearable");
+        value.set_pinned(None);
+        assert_eq!(value.note(), None);
+        assert!(matches!(
+            value.get("pinned"),
+            Ok(Property::Single(pinned)) if pinned.is_empty()
+        ));
+    }
+}
@@ -22,7 +22,7 @@ pub fn implementation(ast: &syn::DeriveInput) -> TokenStream {
 
         let mut simple: Punctuated<_, Token![,]> = Punctuated::new();
 
-        for g in generics.params.iter() {
+        for g in &generics.params {
             let g = match g {
                 syn::GenericParam::Type(syn::TypeParam { ident, .. }) => {
                     syn::GenericParam::Type(syn::TypeParam {
@@ -70,6 +70,7 @@ pub fn implementation(ast: &syn::DeriveInput) -> TokenStream {
 
     let gen = quote! {
         impl #generic #name #generic_simple #where_clause {
+             #[allow(clippy::unnecessary_box_returns)]
              pub fn build(#(#fields),*) -> Box<Self>{
                 Box::new(Self { #(#field_idents),* })
              }
@@ -29,9 +29,16 @@ pub fn build_command_derive(input: TokenStream) -> TokenStream {
 
 /// Derive a storeable implementation for a macro.
 ///
+/// Fields marked as `optional` may be absent when parsing, `typed` fields are parsed using
+/// [`FromStr`](std::str::FromStr) and may be given a value used when absent with
+/// `default_value`, which is not named `default` to not clash with `#[derive(Default)]`.
+///
 /// # Panics
 /// If the struct is malformed.
-#[proc_macro_derive(Storeable, attributes(line, string, composite, token, title))]
+#[proc_macro_derive(
+    Storeable,
+    attributes(line, string, composite, typed, token, title, optional, default_value)
+)]
 pub fn storeable_derive(input: TokenStream) -> TokenStream {
     let ast = syn::parse(input).expect("could not parse struct");
 
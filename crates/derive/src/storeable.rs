@@ -6,6 +6,7 @@ pub mod conversion_boilerplate;
 pub mod display_implementation;
 pub mod field_list;
 pub mod field_single;
+pub mod field_typed;
 pub mod get_name_impl;
 pub mod parse_attr;
 pub mod parse_field;
@@ -30,7 +31,7 @@ pub fn implementation(ast: &syn::DeriveInput) -> TokenStream {
     let conversions = conversion_boilerplate(&name);
 
     let display_implementation =
-        display_implementation(&name, &store_fields, &display_fields, &title_field);
+        display_implementation(&name, &store_fields, &display_fields, title_field.as_ref());
 
     let storeable_impl = gen_storeable_impl(&name, &line, &store_fields);
 
@@ -6,15 +6,46 @@ pub trait AnyField {
     fn get_push_match(&self) -> TokenStream2;
     fn get_field_methods(&self, line: &syn::Ident) -> TokenStream2;
     fn get_create_line_param(&self) -> TokenStream2;
-    fn get_create_line_format_param(&self) -> TokenStream2;
+    fn get_create_line_push(&self, out: &syn::Ident) -> TokenStream2;
+    fn get_new_param(&self) -> Option<TokenStream2>;
     fn get_new_init(&self, line: &syn::Ident) -> TokenStream2;
     fn get_set_match(&self) -> TokenStream2;
     fn get_get_match(&self) -> TokenStream2;
+    fn get_any_value_match(&self) -> TokenStream2;
     fn get_to_line_call(&self) -> TokenStream2;
     fn get_capture_extract(&self, line: &syn::Ident) -> TokenStream2;
     fn get_fancy_display(&self, index: usize) -> TokenStream2;
     fn get_simple_display(&self, index: usize) -> TokenStream2;
 
+    /// Whether or not the token of the field may be absent when parsing.
+    fn is_optional(&self) -> bool;
+
+    /// Whether or not the content of the field is stored in the line.
+    fn is_line_backed(&self) -> bool {
+        true
+    }
+
+    fn get_unescape(&self, line: &syn::Ident, into: &syn::Ident) -> TokenStream2 {
+        let ident = self.get_ident();
+        quote::quote! {
+            #ident.unescape_into(&#line, &mut #into)
+        }
+    }
+
+    fn get_content_len(&self) -> TokenStream2 {
+        let ident = self.get_ident();
+        quote::quote! {
+            self.#ident.content_len()
+        }
+    }
+
+    fn get_compact_copy(&self, line: &syn::Ident, into: &syn::Ident) -> TokenStream2 {
+        let ident = self.get_ident();
+        quote::quote! {
+            let #ident = self.#ident.copy_into(&self.#line, &mut #into);
+        }
+    }
+
     fn get_field_init(&self) -> TokenStream2 {
         let ident = self.get_ident();
         quote::quote! {
@@ -62,7 +62,7 @@ pub fn display_implementation(
     name: &syn::Ident,
     store_fields: &[Box<dyn AnyField>],
     display_fields: &[Box<dyn AnyField>],
-    title_field: &Option<FieldSingle>,
+    title_field: Option<&FieldSingle>,
 ) -> TokenStream2 {
     if let Some(title_field) = title_field {
         gen_display(name, store_fields, display_fields, title_field)
@@ -7,6 +7,7 @@ pub struct FieldList {
     pub ident: syn::Ident,
     pub key: TokenStream2,
     pub singular: syn::Ident,
+    pub optional: bool,
 }
 
 impl FieldList {
@@ -24,6 +25,7 @@ impl FieldList {
             #[doc = #doc_str]
             pub fn #push_ident(&mut self, #single_ident: &str) -> &mut Self {
                 self.#ident.push(self.#line.push(#single_ident).into());
+                bookmark_storage::Storeable::compact_if_wasteful(self);
 
                 self
             }
@@ -60,6 +62,7 @@ impl FieldList {
                 for item in #ident {
                     self.#ident.push(self.#line.push(item.as_ref()).into());
                 }
+                bookmark_storage::Storeable::compact_if_wasteful(self);
 
                 self
             }
@@ -68,24 +71,57 @@ impl FieldList {
 }
 
 impl AnyField for FieldList {
+    fn is_optional(&self) -> bool {
+        self.optional
+    }
+
     fn get_capture_extract(&self, line: &syn::Ident) -> TokenStream2 {
         let ident = &self.ident;
-        quote! {
-            let #ident = bookmark_storage::pattern_match::split_list_field(&#line[start..end])
-                .map(|f| f + start)
-                .collect();
+        let split = quote! {
+            bookmark_storage::pattern_match::split_list_field(&#line[range.clone()])
+                .map(|f| f + range.start)
+                .collect()
+        };
+        if self.optional {
+            quote! {
+                let #ident: bookmark_storage::ListField = range
+                    .map(|range| #split)
+                    .unwrap_or_default();
+            }
+        } else {
+            quote! {
+                let #ident: bookmark_storage::ListField = #split;
+            }
         }
     }
 
-    fn get_create_line_format_param(&self) -> TokenStream2 {
+    fn get_create_line_push(&self, out: &syn::Ident) -> TokenStream2 {
         let token = self.key.clone();
         let ident = &self.ident;
-        quote! {
-            #token,
-            bookmark_storage::pattern_match::join_with_delim(#ident),
+        let joined = quote! {
+            bookmark_storage::pattern_match::join_with_delim(
+                #ident.map(|item| bookmark_storage::pattern_match::escape(item.as_ref()).into_owned())
+            )
+        };
+
+        if self.optional {
+            quote! {
+                let #ident = #joined;
+                if !#ident.is_empty() {
+                    bookmark_storage::pattern_match::push_token(&mut #out, #token, &#ident);
+                }
+            }
+        } else {
+            quote! {
+                bookmark_storage::pattern_match::push_token(&mut #out, #token, &#joined);
+            }
         }
     }
 
+    fn get_new_param(&self) -> Option<TokenStream2> {
+        (!self.optional).then(|| self.get_create_line_param())
+    }
+
     fn get_create_line_param(&self) -> TokenStream2 {
         let ident = &self.ident;
         quote! {#ident: impl Iterator<Item = impl AsRef<str>>,}
@@ -124,6 +160,14 @@ impl AnyField for FieldList {
         }
     }
 
+    fn get_any_value_match(&self) -> TokenStream2 {
+        let ident = &self.ident;
+        let ident_string = self.get_ident_string();
+        quote! {
+            #ident_string => self.#ident().any(|value| predicate(value)),
+        }
+    }
+
     fn get_ident(&self) -> &syn::Ident {
         &self.ident
     }
@@ -134,7 +178,11 @@ impl AnyField for FieldList {
 
     fn get_new_init(&self, line: &syn::Ident) -> TokenStream2 {
         let ident = &self.ident;
-        quote! {#ident: #line.extend(#ident).into(),}
+        if self.optional {
+            quote! {#ident: bookmark_storage::ListField::new(),}
+        } else {
+            quote! {#ident: #line.extend(#ident).into(),}
+        }
     }
 
     fn get_push_match(&self) -> TokenStream2 {
@@ -6,6 +6,7 @@ use quote::quote;
 pub struct FieldSingle {
     pub ident: syn::Ident,
     pub key: TokenStream2,
+    pub optional: bool,
 }
 
 impl FieldSingle {
@@ -16,16 +17,29 @@ impl FieldSingle {
         }
     }
 
+    fn get_clear_ident(&self) -> syn::Ident {
+        quote::format_ident!("clear_{}", self.ident)
+    }
+
     fn get_get_method(&self, line: &syn::Ident) -> TokenStream2 {
         let ident = &self.ident;
         let doc_str = format!(
             "Get the contents of the {ident} field, roughly equivalent to using get(\"{ident}\")."
         );
 
-        quote! {
-            #[doc = #doc_str]
-            pub fn #ident(&self) -> &str {
-                self.#ident.get(&self.#line)
+        if self.optional {
+            quote! {
+                #[doc = #doc_str]
+                pub fn #ident(&self) -> Option<&str> {
+                    self.#ident.as_ref().map(|#ident| #ident.get(&self.#line))
+                }
+            }
+        } else {
+            quote! {
+                #[doc = #doc_str]
+                pub fn #ident(&self) -> &str {
+                    self.#ident.get(&self.#line)
+                }
             }
         }
     }
@@ -35,10 +49,37 @@ impl FieldSingle {
         let set_ident = self.get_set_ident();
         let doc_str = format!("Set the contents of the {ident} field, roughly equivalent to using set(\"{ident}\", {ident}).");
 
+        let value = if self.optional {
+            quote! {Some(self.#line.push(#ident).into())}
+        } else {
+            quote! {self.#line.push(#ident).into()}
+        };
+
         quote! {
             #[doc = #doc_str]
             pub fn #set_ident(&mut self, #ident: &str) -> &mut Self {
-                self.#ident = self.#line.push(#ident).into();
+                self.#ident = #value;
+                bookmark_storage::Storeable::compact_if_wasteful(self);
+
+                self
+            }
+        }
+    }
+
+    fn get_clear_method(&self, line: &syn::Ident) -> TokenStream2 {
+        if !self.optional {
+            return TokenStream2::default();
+        }
+
+        let ident = &self.ident;
+        let clear_ident = self.get_clear_ident();
+        let doc_str = format!("Remove the contents of the {ident} field, roughly equivalent to using set(\"{ident}\", \"\").");
+
+        quote! {
+            #[doc = #doc_str]
+            pub fn #clear_ident(&mut self) -> &mut Self {
+                self.#ident = None;
+                self.#line.touch();
 
                 self
             }
@@ -55,79 +96,202 @@ impl AnyField for FieldSingle {
         &self.ident
     }
 
+    fn is_optional(&self) -> bool {
+        self.optional
+    }
+
     fn get_push_match(&self) -> TokenStream2 {
         TokenStream2::default()
     }
 
     fn get_create_line_param(&self) -> TokenStream2 {
         let ident = &self.ident;
-        quote! {#ident: &str,}
+        if self.optional {
+            quote! {#ident: Option<&str>,}
+        } else {
+            quote! {#ident: &str,}
+        }
     }
 
-    fn get_create_line_format_param(&self) -> TokenStream2 {
+    fn get_create_line_push(&self, out: &syn::Ident) -> TokenStream2 {
         let token = self.key.clone();
         let ident = &self.ident;
-        quote! {
-            #token,
-            #ident,
+        let push = quote! {
+            bookmark_storage::pattern_match::push_token(
+                &mut #out,
+                #token,
+                &bookmark_storage::pattern_match::escape(#ident),
+            );
+        };
+
+        if self.optional {
+            quote! {
+                if let Some(#ident) = #ident {
+                    #push
+                }
+            }
+        } else {
+            push
         }
     }
 
+    fn get_new_param(&self) -> Option<TokenStream2> {
+        (!self.optional).then(|| self.get_create_line_param())
+    }
+
     fn get_new_init(&self, line: &syn::Ident) -> TokenStream2 {
         let ident = &self.ident;
-        quote! {#ident: #line.push(#ident).into(),}
+        if self.optional {
+            quote! {#ident: None,}
+        } else {
+            quote! {#ident: #line.push(#ident).into(),}
+        }
     }
 
     fn get_set_match(&self) -> TokenStream2 {
         let set_ident = self.get_set_ident();
         let ident_string = self.get_ident_string();
 
-        quote! {(#ident_string, bookmark_storage::Property::Single(value)) => {
-            self.#set_ident(&value);
-        }}
+        if self.optional {
+            let clear_ident = self.get_clear_ident();
+            quote! {(#ident_string, bookmark_storage::Property::Single(value)) => {
+                if value.is_empty() {
+                    self.#clear_ident();
+                } else {
+                    self.#set_ident(&value);
+                }
+            }}
+        } else {
+            quote! {(#ident_string, bookmark_storage::Property::Single(value)) => {
+                self.#set_ident(&value);
+            }}
+        }
     }
 
     fn get_get_match(&self) -> TokenStream2 {
         let ident = &self.ident;
         let ident_string = self.get_ident_string();
+        let value = if self.optional {
+            quote! {self.#ident().unwrap_or_default()}
+        } else {
+            quote! {self.#ident()}
+        };
         quote! {
             #ident_string => {
-                bookmark_storage::Property::Single(self.#ident().into())
+                bookmark_storage::Property::Single(#value.into())
             }
         }
     }
 
+    fn get_any_value_match(&self) -> TokenStream2 {
+        let ident = &self.ident;
+        let ident_string = self.get_ident_string();
+        let value = if self.optional {
+            quote! {self.#ident().unwrap_or_default()}
+        } else {
+            quote! {self.#ident()}
+        };
+        quote! {
+            #ident_string => predicate(#value),
+        }
+    }
+
     fn get_to_line_call(&self) -> TokenStream2 {
         let ident = &self.ident;
-        quote! {&self.#ident()}
+        if self.optional {
+            quote! {self.#ident()}
+        } else {
+            quote! {&self.#ident()}
+        }
     }
 
     fn get_capture_extract(&self, line: &syn::Ident) -> TokenStream2 {
         let ident = &self.ident;
-        quote! {
-            let #ident = bookmark_storage::pattern_match::range_trim(&#line, start..end).into();
+        if self.optional {
+            quote! {
+                let #ident: Option<bookmark_storage::Field> = range
+                    .map(|range| bookmark_storage::pattern_match::range_trim(&#line, range).into());
+            }
+        } else {
+            quote! {
+                let #ident: bookmark_storage::Field =
+                    bookmark_storage::pattern_match::range_trim(&#line, range).into();
+            }
+        }
+    }
+
+    fn get_unescape(&self, line: &syn::Ident, into: &syn::Ident) -> TokenStream2 {
+        let ident = &self.ident;
+        if self.optional {
+            quote! {
+                #ident.map(|#ident| #ident.unescape_into(&#line, &mut #into))
+            }
+        } else {
+            quote! {
+                #ident.unescape_into(&#line, &mut #into)
+            }
+        }
+    }
+
+    fn get_content_len(&self) -> TokenStream2 {
+        let ident = &self.ident;
+        if self.optional {
+            quote! {
+                self.#ident.as_ref().map_or(0, bookmark_storage::Field::content_len)
+            }
+        } else {
+            quote! {
+                self.#ident.content_len()
+            }
+        }
+    }
+
+    fn get_compact_copy(&self, line: &syn::Ident, into: &syn::Ident) -> TokenStream2 {
+        let ident = &self.ident;
+        if self.optional {
+            quote! {
+                let #ident = self
+                    .#ident
+                    .as_ref()
+                    .map(|#ident| #ident.copy_into(&self.#line, &mut #into));
+            }
+        } else {
+            quote! {
+                let #ident = self.#ident.copy_into(&self.#line, &mut #into);
+            }
         }
     }
 
     fn get_fancy_display(&self, _: usize) -> TokenStream2 {
         let ident = &self.ident;
         let format_string = format!("\n\t{ident}: {{}}");
-        quote! {
-            write!(f, #format_string, self.#ident())?;
+        if self.optional {
+            quote! {
+                if let Some(#ident) = self.#ident() {
+                    write!(f, #format_string, #ident)?;
+                }
+            }
+        } else {
+            quote! {
+                write!(f, #format_string, self.#ident())?;
+            }
         }
     }
 
     fn get_simple_display(&self, index: usize) -> TokenStream2 {
         let ident = &self.ident;
         let key = self.key.clone();
+        let format_string = if index == 0 { "{} {}" } else { " {} {}" };
 
-        if index == 0 {
+        if self.optional {
             quote! {
-                write!(f, "{} {}", #key, self.#ident())?;
+                if let Some(#ident) = self.#ident() {
+                    write!(f, #format_string, #key, #ident)?;
+                }
             }
         } else {
             quote! {
-                write!(f, " {} {}", #key, self.#ident())?;
+                write!(f, #format_string, #key, self.#ident())?;
             }
         }
     }
@@ -135,10 +299,12 @@ impl AnyField for FieldSingle {
     fn get_field_methods(&self, line: &syn::Ident) -> TokenStream2 {
         let set_fn = self.get_set_method(line);
         let get_fn = self.get_get_method(line);
+        let clear_fn = self.get_clear_method(line);
 
         quote! {
             #set_fn
             #get_fn
+            #clear_fn
         }
     }
 }
@@ -0,0 +1,301 @@
+use crate::storeable::any_field::AnyField;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+
+/// A field parsed using [`FromStr`](std::str::FromStr) and stored outside of the line.
+#[derive(Debug, Clone)]
+pub struct FieldTyped {
+    pub ident: syn::Ident,
+    pub key: TokenStream2,
+    /// Type of the value, without any [Option].
+    pub ty: syn::Type,
+    /// Whether or not the field is an [Option] of the type.
+    pub optional: bool,
+    pub default: Option<syn::Expr>,
+}
+
+impl FieldTyped {
+    fn get_value_type(&self) -> TokenStream2 {
+        let ty = &self.ty;
+        if self.optional {
+            quote! {Option<#ty>}
+        } else {
+            quote! {#ty}
+        }
+    }
+
+    fn get_absent_value(&self) -> TokenStream2 {
+        if let Some(ref default) = self.default {
+            quote! {#default}
+        } else {
+            quote! {None}
+        }
+    }
+
+    fn get_get_method(&self) -> TokenStream2 {
+        let ident = &self.ident;
+        let value_type = self.get_value_type();
+        let doc_str = format!(
+            "Get the value of the {ident} field, roughly equivalent to using get(\"{ident}\")."
+        );
+
+        quote! {
+            #[doc = #doc_str]
+            pub fn #ident(&self) -> #value_type {
+                self.#ident.clone()
+            }
+        }
+    }
+
+    fn get_set_method(&self, line: &syn::Ident) -> TokenStream2 {
+        let ident = &self.ident;
+        let set_ident = self.get_set_ident();
+        let value_type = self.get_value_type();
+        let doc_str = format!("Set the value of the {ident} field, roughly equivalent to using set(\"{ident}\", {ident}).");
+
+        quote! {
+            #[doc = #doc_str]
+            pub fn #set_ident(&mut self, #ident: #value_type) -> &mut Self {
+                self.#ident = #ident;
+                self.#line.touch();
+
+                self
+            }
+        }
+    }
+}
+
+impl AnyField for FieldTyped {
+    fn get_key(&self) -> TokenStream2 {
+        self.key.clone()
+    }
+
+    fn get_ident(&self) -> &syn::Ident {
+        &self.ident
+    }
+
+    fn is_optional(&self) -> bool {
+        self.optional || self.default.is_some()
+    }
+
+    fn is_line_backed(&self) -> bool {
+        false
+    }
+
+    fn get_push_match(&self) -> TokenStream2 {
+        TokenStream2::default()
+    }
+
+    fn get_create_line_param(&self) -> TokenStream2 {
+        let ident = &self.ident;
+        let ty = &self.ty;
+        if self.optional {
+            quote! {#ident: Option<&#ty>,}
+        } else {
+            quote! {#ident: &#ty,}
+        }
+    }
+
+    fn get_create_line_push(&self, out: &syn::Ident) -> TokenStream2 {
+        let token = self.key.clone();
+        let ident = &self.ident;
+        let push = quote! {
+            bookmark_storage::pattern_match::push_token(
+                &mut #out,
+                #token,
+                &bookmark_storage::pattern_match::escape(&#ident.to_string()),
+            );
+        };
+
+        if self.optional {
+            quote! {
+                if let Some(#ident) = #ident {
+                    #push
+                }
+            }
+        } else {
+            push
+        }
+    }
+
+    fn get_new_param(&self) -> Option<TokenStream2> {
+        let ident = &self.ident;
+        let ty = &self.ty;
+        (!self.is_optional()).then(|| quote! {#ident: #ty,})
+    }
+
+    fn get_new_init(&self, _: &syn::Ident) -> TokenStream2 {
+        let ident = &self.ident;
+        if self.is_optional() {
+            let value = self.get_absent_value();
+            quote! {#ident: #value,}
+        } else {
+            quote! {#ident,}
+        }
+    }
+
+    fn get_set_match(&self) -> TokenStream2 {
+        let ident = &self.ident;
+        let ident_string = self.get_ident_string();
+        let ty = &self.ty;
+
+        let parse = quote! {
+            value.parse::<#ty>().map_err(|err| bookmark_storage::PropertyErr::InvalidValue {
+                property: property.into(),
+                value: value.clone(),
+                reason: err.to_string(),
+            })?
+        };
+
+        let value = if self.optional {
+            quote! {
+                if value.is_empty() {
+                    None
+                } else {
+                    Some(#parse)
+                }
+            }
+        } else {
+            parse
+        };
+
+        let set_ident = self.get_set_ident();
+        quote! {(#ident_string, bookmark_storage::Property::Single(value)) => {
+            let #ident = #value;
+            self.#set_ident(#ident);
+        }}
+    }
+
+    fn get_get_match(&self) -> TokenStream2 {
+        let ident = &self.ident;
+        let ident_string = self.get_ident_string();
+        let value = if self.optional {
+            quote! {self.#ident.as_ref().map(ToString::to_string).unwrap_or_default()}
+        } else {
+            quote! {self.#ident.to_string()}
+        };
+        quote! {
+            #ident_string => {
+                bookmark_storage::Property::Single(#value)
+            }
+        }
+    }
+
+    fn get_any_value_match(&self) -> TokenStream2 {
+        let ident = &self.ident;
+        let ident_string = self.get_ident_string();
+        let value = if self.optional {
+            quote! {self.#ident.as_ref().map(ToString::to_string).unwrap_or_default()}
+        } else {
+            quote! {self.#ident.to_string()}
+        };
+        quote! {
+            #ident_string => predicate(&#value),
+        }
+    }
+
+    fn get_to_line_call(&self) -> TokenStream2 {
+        let ident = &self.ident;
+        if self.optional {
+            quote! {self.#ident.as_ref()}
+        } else {
+            quote! {&self.#ident}
+        }
+    }
+
+    fn get_capture_extract(&self, line: &syn::Ident) -> TokenStream2 {
+        let ident = &self.ident;
+        let ty = &self.ty;
+        let key = self.key.clone();
+        let absent = self.get_absent_value();
+
+        let parse = quote! {
+            {
+                let range = bookmark_storage::pattern_match::range_trim(&#line, range);
+                let found = bookmark_storage::pattern_match::unescape(&#line[range.clone()]);
+                found.parse::<#ty>().map_err(|_| bookmark_storage::ParseErr::InvalidValue {
+                    location: location(range.start),
+                    expected: #key.into(),
+                    found: found.clone().into_owned(),
+                })?
+            }
+        };
+
+        let value_type = self.get_value_type();
+        if !self.is_optional() {
+            quote! {
+                let #ident: #value_type = #parse;
+            }
+        } else if self.optional {
+            quote! {
+                let #ident: #value_type = match range {
+                    Some(range) => Some(#parse),
+                    None => #absent,
+                };
+            }
+        } else {
+            quote! {
+                let #ident: #value_type = match range {
+                    Some(range) => #parse,
+                    None => #absent,
+                };
+            }
+        }
+    }
+
+    fn get_unescape(&self, _: &syn::Ident, _: &syn::Ident) -> TokenStream2 {
+        TokenStream2::default()
+    }
+
+    fn get_content_len(&self) -> TokenStream2 {
+        TokenStream2::default()
+    }
+
+    fn get_compact_copy(&self, _: &syn::Ident, _: &syn::Ident) -> TokenStream2 {
+        TokenStream2::default()
+    }
+
+    fn get_fancy_display(&self, _: usize) -> TokenStream2 {
+        let ident = &self.ident;
+        let format_string = format!("\n\t{ident}: {{}}");
+        if self.optional {
+            quote! {
+                if let Some(ref #ident) = self.#ident {
+                    write!(f, #format_string, #ident)?;
+                }
+            }
+        } else {
+            quote! {
+                write!(f, #format_string, self.#ident)?;
+            }
+        }
+    }
+
+    fn get_simple_display(&self, index: usize) -> TokenStream2 {
+        let ident = &self.ident;
+        let key = self.key.clone();
+        let format_string = if index == 0 { "{} {}" } else { " {} {}" };
+
+        if self.optional {
+            quote! {
+                if let Some(ref #ident) = self.#ident {
+                    write!(f, #format_string, #key, #ident)?;
+                }
+            }
+        } else {
+            quote! {
+                write!(f, #format_string, #key, self.#ident)?;
+            }
+        }
+    }
+
+    fn get_field_methods(&self, line: &syn::Ident) -> TokenStream2 {
+        let get_fn = self.get_get_method();
+        let set_fn = self.get_set_method(line);
+
+        quote! {
+            #get_fn
+            #set_fn
+        }
+    }
+}
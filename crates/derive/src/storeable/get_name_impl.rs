@@ -13,13 +13,10 @@ fn gen_field_access(line: &syn::Ident, store_fields: &[Box<dyn AnyField>]) -> To
     }
 }
 
-fn gen_new(
-    line: &syn::Ident,
-    store_fields: &[Box<dyn AnyField>],
-    params: &Vec<TokenStream2>,
-) -> TokenStream2 {
+fn gen_new(line: &syn::Ident, store_fields: &[Box<dyn AnyField>]) -> TokenStream2 {
+    let params = store_fields.iter().filter_map(|f| f.get_new_param());
     let new_fields = store_fields.iter().map(|f| f.get_new_init(line));
-    let doc_str = "Create a new instance with provided fields.";
+    let doc_str = "Create a new instance with provided fields, optional fields are left unset.";
 
     quote! {
         #[doc = #doc_str]
@@ -33,18 +30,11 @@ fn gen_new(
     }
 }
 
-fn gen_create_line(
-    store_fields: &Vec<Box<dyn AnyField>>,
-    params: &Vec<TokenStream2>,
-) -> TokenStream2 {
-    let format_params = store_fields
-        .iter()
-        .map(|f| f.get_create_line_format_param());
+fn gen_create_line(store_fields: &[Box<dyn AnyField>]) -> TokenStream2 {
+    let params = store_fields.iter().map(|f| f.get_create_line_param());
 
-    let format_string = std::iter::repeat("{}")
-        .take(store_fields.len() * 2)
-        .collect::<Vec<_>>()
-        .join(" ");
+    let out = quote::format_ident!("out");
+    let pushes = store_fields.iter().map(|f| f.get_create_line_push(&out));
 
     let doc_str =
         "create a line of text based on how an instance of this struct would look when stored.";
@@ -52,7 +42,11 @@ fn gen_create_line(
     quote! {
         #[doc = #doc_str]
         pub fn create_line<'a>(#(#params)*) -> String {
-            format!(#format_string, #(#format_params)*)
+            let mut #out = String::new();
+            #(
+                #pushes
+            )*
+            #out
         }
     }
 }
@@ -62,13 +56,8 @@ pub fn gen_name_impl(
     line: &syn::Ident,
     store_fields: &Vec<Box<dyn AnyField>>,
 ) -> TokenStream2 {
-    let params = store_fields
-        .iter()
-        .map(|f| f.get_create_line_param())
-        .collect::<Vec<_>>();
-
-    let create_line_fn = gen_create_line(store_fields, &params);
-    let new_fn = gen_new(line, store_fields, &params);
+    let create_line_fn = gen_create_line(store_fields);
+    let new_fn = gen_new(line, store_fields);
     let field_access = gen_field_access(line, store_fields);
 
     quote! {
@@ -8,10 +8,20 @@ pub enum AttrType {
     Content,
     Key(TokenStream2),
     Title,
+    Optional,
+    Typed,
+    Default(Box<syn::Expr>),
     Other,
 }
 
 pub fn parse_attr(attr: &syn::Attribute) -> AttrType {
+    if attr.path.is_ident("default_value") {
+        let Ok(expr) = attr.parse_args::<syn::Expr>() else {
+            panic!("contents of default_value should be a single expression");
+        };
+        return AttrType::Default(Box::new(expr));
+    }
+
     let Ok(meta) = attr.parse_meta() else {
         return AttrType::Other;
     };
@@ -25,6 +35,8 @@ pub fn parse_attr(attr: &syn::Attribute) -> AttrType {
             "line" => return AttrType::Content,
             "string" => return AttrType::Single,
             "title" => return AttrType::Title,
+            "optional" => return AttrType::Optional,
+            "typed" => return AttrType::Typed,
             _ => return AttrType::Other,
         }
     }
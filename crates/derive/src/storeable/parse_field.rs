@@ -1,6 +1,7 @@
 use crate::storeable::{
     field_list::FieldList,
     field_single::FieldSingle,
+    field_typed::FieldTyped,
     parse_attr::{parse_attr, AttrType},
 };
 
@@ -8,10 +9,29 @@ use crate::storeable::{
 pub enum FieldType {
     Single(FieldSingle, bool),
     List(FieldList),
+    Typed(Box<FieldTyped>),
     Content(syn::Ident),
     Other,
 }
 
+/// Get the inner type of a type if it is an [Option].
+fn option_inner(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Path(syn::TypePath { qself: None, path }) = ty else {
+        return None;
+    };
+    let segment = path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(ref args) = segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        syn::GenericArgument::Type(ty) if args.args.len() == 1 => Some(ty),
+        _ => None,
+    }
+}
+
 pub fn parse_field(field: &syn::Field) -> FieldType {
     let ident = field
         .ident
@@ -21,6 +41,8 @@ pub fn parse_field(field: &syn::Field) -> FieldType {
     let mut attr_type = AttrType::Other;
     let mut key = None;
     let mut is_title = false;
+    let mut is_optional = false;
+    let mut default = None;
 
     for attr in &field.attrs {
         match parse_attr(attr) {
@@ -35,18 +57,18 @@ pub fn parse_field(field: &syn::Field) -> FieldType {
             AttrType::Title => {
                 is_title = true;
             }
-            AttrType::Single => {
-                if matches!(attr_type, AttrType::Other) {
-                    attr_type = AttrType::Single;
-                } else {
-                    panic!("field type may be either string or composite")
-                }
+            AttrType::Optional => {
+                is_optional = true;
+            }
+            AttrType::Default(expr) => {
+                assert!(default.is_none(), "a field may only have one default_value");
+                default = Some(expr);
             }
-            AttrType::List { singular } => {
+            kind @ (AttrType::Single | AttrType::List { .. } | AttrType::Typed) => {
                 if matches!(attr_type, AttrType::Other) {
-                    attr_type = AttrType::List { singular }
+                    attr_type = kind;
                 } else {
-                    panic!("field type may be either string or composite")
+                    panic!("field type may be either string, composite or typed")
                 }
             }
             AttrType::Other => (),
@@ -57,6 +79,14 @@ pub fn parse_field(field: &syn::Field) -> FieldType {
         matches!(attr_type, AttrType::Single) || !is_title,
         "title may only be specified on a field marked as string"
     );
+    assert!(
+        !(is_title && is_optional),
+        "a field marked as title may not be optional"
+    );
+    assert!(
+        matches!(attr_type, AttrType::Typed) || default.is_none(),
+        "default_value may only be specified on a field marked as typed"
+    );
 
     if matches!(attr_type, AttrType::Other) {
         return FieldType::Other;
@@ -71,12 +101,38 @@ pub fn parse_field(field: &syn::Field) -> FieldType {
     };
 
     match attr_type {
-        AttrType::Single => FieldType::Single(FieldSingle { ident, key }, is_title),
+        AttrType::Single => FieldType::Single(
+            FieldSingle {
+                ident,
+                key,
+                optional: is_optional,
+            },
+            is_title,
+        ),
         AttrType::List { singular } => FieldType::List(FieldList {
             ident,
             key,
             singular,
+            optional: is_optional,
         }),
+        AttrType::Typed => {
+            let inner = option_inner(&field.ty);
+            assert!(
+                !is_optional || inner.is_some(),
+                "optional typed fields should be of type Option"
+            );
+            assert!(
+                inner.is_none() || default.is_none(),
+                "typed fields of type Option may not have a default_value"
+            );
+            FieldType::Typed(Box::new(FieldTyped {
+                ident,
+                key,
+                ty: inner.unwrap_or(&field.ty).clone(),
+                optional: inner.is_some(),
+                default: default.map(|default| *default),
+            }))
+        }
         _ => std::unreachable!(),
     }
 }
@@ -25,7 +25,7 @@ pub fn parse_struct(ast: &syn::DeriveInput) -> StructInfo {
         panic!("Storeable should only be derived on structs");
     };
 
-    for field in data.fields.iter() {
+    for field in &data.fields {
         match parse_field(field) {
             FieldType::Content(ident) => {
                 if line.is_none() {
@@ -50,7 +50,11 @@ pub fn parse_struct(ast: &syn::DeriveInput) -> StructInfo {
                 store_fields.push(Box::new(field.clone()));
                 display_fields.push(Box::new(field.clone()));
             }
-            FieldType::Other => continue,
+            FieldType::Typed(field) => {
+                store_fields.push(field.clone());
+                display_fields.push(field);
+            }
+            FieldType::Other => (),
         }
     }
 
@@ -2,6 +2,14 @@ use super::any_field::AnyField;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 
+fn gen_properties(store_fields: &[Box<dyn AnyField>]) -> TokenStream2 {
+    let properties = store_fields.iter().map(|f| f.get_ident_string());
+
+    quote! {
+        const PROPERTIES: &'static [&'static str] = &[#(#properties),*];
+    }
+}
+
 fn gen_set(store_fields: &[Box<dyn AnyField>]) -> TokenStream2 {
     let set_matches = store_fields.iter().map(|f| f.get_set_match());
 
@@ -40,6 +48,25 @@ fn gen_get(store_fields: &[Box<dyn AnyField>]) -> TokenStream2 {
     }
 }
 
+fn gen_any_value(store_fields: &[Box<dyn AnyField>]) -> TokenStream2 {
+    let any_value_matches = store_fields.iter().map(|f| f.get_any_value_match());
+
+    quote! {
+        fn any_value(
+            &self,
+            property: &str,
+            predicate: &mut dyn FnMut(&str) -> bool,
+        ) -> Result<bool, bookmark_storage::PropertyErr> {
+            Ok(match property {
+                #(
+                    #any_value_matches
+                )*
+                _ => return Err(bookmark_storage::PropertyErr::DoesNotExist(property.into())),
+            })
+        }
+    }
+}
+
 fn gen_push(store_fields: &[Box<dyn AnyField>]) -> TokenStream2 {
     let push_matches = store_fields.iter().map(|f| f.get_push_match());
 
@@ -60,11 +87,71 @@ fn gen_push(store_fields: &[Box<dyn AnyField>]) -> TokenStream2 {
     }
 }
 
+fn gen_capture(index: usize, line: &syn::Ident, field: &dyn AnyField) -> TokenStream2 {
+    let capture_extract = field.get_capture_extract(line);
+
+    let require = if field.is_optional() {
+        TokenStream2::default()
+    } else {
+        quote! {
+            let Some(range) = range else {
+                return Err(match iter.peek() {
+                    Some(mat) => bookmark_storage::ParseErr::UnexpectedToken {
+                        location: location(mat.start()),
+                        expected: TOKENS[#index].into(),
+                        found: #line[mat.start()..mat.end()].into(),
+                    },
+                    None => bookmark_storage::ParseErr::MissingToken {
+                        location: location(len()),
+                        expected: TOKENS[#index].into(),
+                    },
+                });
+            };
+        }
+    };
+
+    quote! {
+        let range = if let Some(mat) = iter.next_if(|mat| mat.pattern() == #index) {
+            let start = mat.end();
+            let end = iter.peek().map(|m| m.start()).unwrap_or_else(len);
+            if start > end {
+                return Err(bookmark_storage::ParseErr::InvalidRange {
+                    location: location(end),
+                    expected: TOKENS[#index].into(),
+                    found: #line[end..start].into(),
+                });
+            }
+            Some(start..end)
+        } else {
+            None
+        };
+        #require
+
+        #capture_extract
+    }
+}
+
 fn gen_with_string(line: &syn::Ident, store_fields: &[Box<dyn AnyField>]) -> TokenStream2 {
-    let capture_extracts = store_fields.iter().map(|f| f.get_capture_extract(line));
+    let captures = store_fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| gen_capture(i, line, f.as_ref()));
 
     let field_init = store_fields.iter().map(|f| f.get_field_init());
 
+    let line_backed = store_fields
+        .iter()
+        .filter(|f| f.is_line_backed())
+        .collect::<Vec<_>>();
+
+    let idents = line_backed
+        .iter()
+        .map(|f| f.get_ident())
+        .collect::<Vec<_>>();
+
+    let into = quote::format_ident!("unescaped");
+    let unescapes = line_backed.iter().map(|f| f.get_unescape(line, &into));
+
     let tokens = store_fields.iter().map(|f| f.get_key());
 
     quote! {
@@ -72,34 +159,49 @@ fn gen_with_string(line: &syn::Ident, store_fields: &[Box<dyn AnyField>]) -> Tok
             #line: bookmark_storage::ContentString,
             line_num: Option<usize>,
         ) -> Result<Self, bookmark_storage::ParseErr> {
-            let err = || bookmark_storage::ParseErr::Line(Some(#line.clone().to_string()), line_num);
+            let location = |column| bookmark_storage::Location::new(line_num, Some(column));
             let len = || #line.len();
 
             use aho_corasick::AhoCorasick;
             use bookmark_storage::Lazy;
-            static AC: Lazy<AhoCorasick> = Lazy::new(|| {
-                AhoCorasick::new(&[
-                    #(#tokens),*
-                ])
-            });
+            static TOKENS: &[&str] = &[
+                #(#tokens),*
+            ];
+            static AC: Lazy<AhoCorasick> = Lazy::new(|| AhoCorasick::new(TOKENS));
 
-            let mut iter = AC.find_iter(&#line).enumerate().peekable();
+            let mut iter = AC.find_iter(&#line).peekable();
 
             #(
-                let (i, mat) = iter.next().ok_or_else(err)?;
-                let start = mat.end();
-                let end = iter.peek().map(|m| m.1.start()).unwrap_or_else(len);
-                if start > end || mat.pattern() != i {
-                    return Err(err());
-                }
-
-                #capture_extracts
+                #captures
             )*
 
-            Ok(Self {
+            if let Some(mat) = iter.next() {
+                return Err(bookmark_storage::ParseErr::UnexpectedToken {
+                    location: location(mat.start()),
+                    expected: "end of line".into(),
+                    found: #line[mat.start()..mat.end()].into(),
+                });
+            }
+
+            let (#line, #(#idents),*) = if #line.contains(bookmark_storage::token::ESCAPE) {
+                let mut #into = String::with_capacity(#line.len());
+                #(
+                    let #idents = #unescapes;
+                )*
+                (bookmark_storage::ContentString::from_string(#into), #(#idents),*)
+            } else {
+                (#line, #(#idents),*)
+            };
+
+            let mut parsed = Self {
                 #line: #line,
                 #(#field_init,)*
-            })
+            };
+            // every byte not used by a field right after parsing is overhead such as tokens
+            let overhead = bookmark_storage::Storeable::waste(&parsed);
+            parsed.#line.set_overhead(overhead);
+
+            Ok(parsed)
         }
     }
 }
@@ -122,6 +224,47 @@ fn gen_is_edited(line: &syn::Ident) -> TokenStream2 {
     }
 }
 
+fn gen_compaction(line: &syn::Ident, store_fields: &[Box<dyn AnyField>]) -> TokenStream2 {
+    let store_fields = store_fields
+        .iter()
+        .filter(|f| f.is_line_backed())
+        .collect::<Vec<_>>();
+
+    let content_lens = store_fields.iter().map(|f| f.get_content_len());
+
+    let compacted = quote::format_ident!("compacted");
+    let compact_copies = store_fields
+        .iter()
+        .map(|f| f.get_compact_copy(line, &compacted));
+
+    let idents = store_fields.iter().map(|f| f.get_ident());
+
+    quote! {
+        fn waste(&self) -> usize {
+            self.#line.len() #(- #content_lens)*
+        }
+
+        fn stale(&self) -> usize {
+            self.waste().saturating_sub(self.#line.overhead())
+        }
+
+        fn stored_len(&self) -> usize {
+            self.#line.len()
+        }
+
+        fn compact(&mut self) {
+            let mut #compacted = String::with_capacity(self.stored_len() - self.waste());
+            #(
+                #compact_copies
+            )*
+            #(
+                self.#idents = #idents;
+            )*
+            self.#line.replace_compacted(#compacted);
+        }
+    }
+}
+
 pub fn gen_storeable_impl(
     name: &syn::Ident,
     line: &syn::Ident,
@@ -130,18 +273,24 @@ pub fn gen_storeable_impl(
     let with_string_fn = gen_with_string(line, store_fields);
     let to_line_fn = gen_to_line(store_fields);
     let is_edited_fn = gen_is_edited(line);
+    let compaction_fns = gen_compaction(line, store_fields);
 
+    let properties_const = gen_properties(store_fields);
     let set_fn = gen_set(store_fields);
     let get_fn = gen_get(store_fields);
+    let any_value_fn = gen_any_value(store_fields);
     let push_fn = gen_push(store_fields);
 
     quote! {
         impl bookmark_storage::Storeable for #name {
+            #properties_const
             #is_edited_fn
+            #compaction_fns
             #to_line_fn
             #with_string_fn
             #set_fn
             #get_fn
+            #any_value_fn
             #push_fn
         }
     }
@@ -79,7 +79,7 @@ impl FileData {
                 url: b.url().into(),
                 info: b.description().into(),
                 tag: b.tags().map(String::from).collect(),
-                uuid: Uuid::new_v4(),
+                uuid: b.id(),
             })
             .collect::<Vec<_>>()
             .tap_mut(|vec| vec.sort_by_key(|b| b.url.clone()))
@@ -95,6 +95,11 @@ impl<'a> From<IdentifierContainer<'a>> for IdentifierData {
             require: value.require.pipe(transform_vec),
             whole: value.whole.pipe(transform_vec),
             include: value.include.pipe(transform_vec),
+            tag: value.tag.pipe(transform_vec),
+            description: value.description.pipe(transform_vec),
+            domain: value.domain.pipe(transform_vec),
+            regex: value.regex.pipe(transform_vec),
+            exclude: value.exclude.pipe(transform_vec),
         }
     }
 }
@@ -137,6 +142,11 @@ struct IdentifierData {
     require: Vec<String>,
     whole: Vec<String>,
     include: Vec<String>,
+    tag: Vec<String>,
+    description: Vec<String>,
+    domain: Vec<String>,
+    regex: Vec<String>,
+    exclude: Vec<String>,
 }
 
 #[derive(Default, Debug, Serialize)]
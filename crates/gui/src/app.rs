@@ -1,10 +1,16 @@
 use crate::{setting_key, MainContent, Msg};
-use aho_corasick::AhoCorasickBuilder;
-use bookmark_library::{command_map::CommandMap, container, shared, Bookmark, Category, Info};
+use bookmark_library::{
+    check_categories,
+    command_map::{CommandMap, Lengths},
+    container, format,
+    query::Query,
+    shared, Bookmark, Category, Info, Timestamp,
+};
 use bookmark_settings::{Settings, SettingsBuilder};
-use bookmark_storage::Listed;
+use bookmark_storage::{Collector, Listed};
 use iced::{
     executor,
+    keyboard::{self, KeyCode},
     widget::{
         self,
         pane_grid::{self, Axis, DragEvent, ResizeEvent},
@@ -15,10 +21,9 @@ use std::{
     cell::RefCell,
     collections::HashMap,
     fs,
-    io::{self, BufRead},
     path::{Path, PathBuf},
     rc::Rc,
-    sync::mpsc,
+    sync::{mpsc, Arc},
 };
 use ui::{
     bookmarks_column::BookmarkColumnState,
@@ -100,44 +105,99 @@ impl App {
 
     fn load_section<T>(
         &self,
-        source: impl Iterator<Item = (usize, Result<String, io::Error>)>,
+        path: &Path,
+        collector: Collector<T>,
         dest: &mut container::BufferStorage<T>,
     ) where
-        T: Listed,
+        T: Listed + Send,
     {
-        self.set_status(match bookmark_storage::load_from(source) {
-            Ok(v) => {
-                dest.storage.as_mut().reserve(v.len());
-                dest.storage.extend(v);
-                format!("loaded section [{}]", T::ITEM_NAME)
-            }
+        let report = match collector.into_report() {
+            Ok(report) => report.with_path(path),
             Err(err) => {
-                format!("failed to load section [{}], {err}", T::ITEM_NAME)
+                self.set_status(format!(
+                    "failed to load section [{}], {}",
+                    T::ITEM_NAME,
+                    err.with_path(path)
+                ));
+                return;
             }
+        };
+
+        dest.storage.as_mut().reserve(report.items.len());
+        dest.storage.extend(report.items);
+
+        for quarantined in &report.quarantined {
+            self.set_status(format!(
+                "quarantined line in section [{}], {quarantined}",
+                T::ITEM_NAME
+            ));
+        }
+
+        self.set_status(if report.quarantined.is_empty() {
+            format!("loaded section [{}]", T::ITEM_NAME)
+        } else {
+            format!(
+                "loaded section [{}], {} lines quarantined",
+                T::ITEM_NAME,
+                report.quarantined.len()
+            )
         });
     }
 
     fn load_file(&mut self, path: &Path) -> &mut Self {
-        let file = match fs::File::open(path) {
-            Ok(file) => file,
+        let before = std::time::Instant::now();
+
+        let content = match fs::read_to_string(path) {
+            Ok(content) => Arc::<str>::from(content),
             Err(err) => {
-                self.set_status(format!("failed to open file \"{}\", {err}", path.display()));
+                self.set_status(format!("failed to read file \"{}\", {err}", path.display()));
                 return self;
             }
         };
 
-        let mut reader = io::BufReader::new(file).lines().enumerate();
+        let threads = self.settings[setting_key::LOAD_THREADS];
+        let lenient = self.settings[setting_key::LENIENT_LOAD];
 
-        macro_rules! load_sections {
-            ($($sect:expr),* $(,)?) => {
-                $(
-                self.load_section(reader.by_ref(), &mut $sect.write());
-                )*
-            };
+        let mut infos = Collector::<Info>::new(lenient).with_threads(threads);
+        let mut categories = Collector::<Category>::new(lenient).with_threads(threads);
+        let mut bookmarks = Collector::<Bookmark>::new(lenient).with_threads(threads);
+
+        let scanned = bookmark_storage::scan_shared(
+            &content,
+            &format::MIGRATIONS,
+            &mut [&mut infos, &mut categories, &mut bookmarks],
+        );
+
+        match scanned {
+            Ok(unknown) => {
+                for section in unknown {
+                    self.set_status(format!("warning: {}", section.with_path(path)));
+                }
+            }
+            Err(err) => {
+                // only errors with a location include the path
+                self.set_status(if err.location().is_some() {
+                    format!("failed to load file, {}", err.with_path(path))
+                } else {
+                    format!("failed to load file \"{}\", {err}", path.display())
+                });
+                return self;
+            }
         }
 
-        let before = std::time::Instant::now();
-        load_sections!(self.infos, self.categories, self.bookmarks,);
+        // recorded as one change such that undo keeps indices of all storages consistent
+        let lengths = Lengths::of(&self.bookmarks, &self.categories, &self.infos);
+        self.load_section(path, infos, &mut self.infos.write());
+        self.load_section(path, categories, &mut self.categories.write());
+        let from = self.bookmarks.read().storage.len();
+        self.load_section(path, bookmarks, &mut self.bookmarks.write());
+        for reassigned in
+            Bookmark::reassign_duplicate_ids(&mut self.bookmarks.write().storage, from)
+        {
+            self.set_status(format!("warning: {reassigned}"));
+        }
+        lengths.record_appended("load", &self.bookmarks, &self.categories, &self.infos);
+
         let duration = std::time::Instant::now().duration_since(before);
 
         self.set_status(format!(
@@ -155,14 +215,9 @@ impl App {
             return;
         }
 
-        let patterns: &[&str] = &[&self.bookmark_column_state.filter_str];
-
-        self.bookmark_column_state.filter = Some(
-            AhoCorasickBuilder::new()
-                .auto_configure(patterns)
-                .ascii_case_insensitive(true)
-                .build(patterns),
-        );
+        // incomplete queries are expected while typing, errors are reported when applied
+        self.bookmark_column_state.filter =
+            Query::parse::<Bookmark>(&self.bookmark_column_state.filter_str).ok();
     }
 
     fn update_category_tree(&mut self) {
@@ -219,7 +274,19 @@ impl App {
         self.category_tree = cat_iter;
     }
 
+    fn check_categories(&self) {
+        let issues = check_categories(&self.categories.read().storage, &self.infos.read().storage);
+
+        if issues.is_empty() {
+            self.set_status("no issues found in categories");
+        }
+        for issue in issues {
+            self.set_status(issue.to_string());
+        }
+    }
+
     fn apply_category(&mut self, indices: impl IntoIterator<Item = usize>) {
+        let recursive = self.settings[setting_key::RECURSIVE_APPLY];
         let messages = {
             let categories = self.categories.read();
             let mut bookmarks = self.bookmarks.write();
@@ -229,8 +296,14 @@ impl App {
                 .map(|i| {
                     let category = &categories.storage[i];
 
-                    match category.apply(&mut bookmarks) {
-                        Ok(_) => format!("applied category <{}>", category.name()),
+                    let applied = if recursive {
+                        category.apply_recursive(&categories.storage, &mut bookmarks)
+                    } else {
+                        category.apply(&mut bookmarks)
+                    };
+
+                    match applied {
+                        Ok(()) => format!("applied category <{}>", category.name()),
                         Err(err) => {
                             format!("failed to apply category <{}>, {}", category.name(), err)
                         }
@@ -246,9 +319,10 @@ impl App {
 
     fn goto_bookmark_location(&self, index: usize) {
         self.set_status({
-            let bookmarks = self.bookmarks.read();
+            let mut bookmarks = self.bookmarks.write();
             match open::that(bookmarks.storage[index].url()) {
                 Ok(()) => {
+                    bookmarks.storage[index].set_visited(Some(Timestamp::now()));
                     format!("opened bookmark [{}]", bookmarks.storage[index].url())
                 }
                 Err(err) => {
@@ -275,7 +349,7 @@ impl App {
         let count = self.edit_column_state.panes.iter().count();
 
         self.edit_column_state.panes.split(
-            if count % 2 == 0 {
+            if count.is_multiple_of(2) {
                 Axis::Horizontal
             } else {
                 Axis::Vertical
@@ -302,7 +376,7 @@ impl App {
         let count = self.edit_column_state.panes.iter().count();
 
         self.edit_column_state.panes.split(
-            if count % 2 == 0 {
+            if count.is_multiple_of(2) {
                 Axis::Horizontal
             } else {
                 Axis::Vertical
@@ -392,6 +466,12 @@ impl Default for App {
                 dark_light::Mode::Light => Theme::Light,
             })
             .add(setting_key::EDIT_MODE_ACTIVE, false)
+            .add(setting_key::RECURSIVE_APPLY, false)
+            .add_fn(setting_key::LOAD_THREADS, || {
+                std::thread::available_parallelism().map_or(1, usize::from)
+            })
+            .add(setting_key::JOURNAL_LIMIT, container::JOURNAL_LIMIT)
+            .add(setting_key::LENIENT_LOAD, false)
             .build();
 
         dbg!(&settings);
@@ -432,21 +512,49 @@ impl Application for App {
 
         app.set_status("Created application");
 
+        app.bookmarks
+            .write()
+            .journal_mut()
+            .set_limit(app.settings[setting_key::JOURNAL_LIMIT]);
+
         for file in flags {
             app.load_file(&file);
         }
 
         app.update_category_tree();
+        app.check_categories();
 
         (app, iced::Command::none())
     }
 
     fn subscription(&self) -> iced::Subscription<Self::Message> {
-        if self.tick_watcher_count > 0 {
+        let shortcuts = iced::subscription::events_with(|event, status| {
+            let (
+                iced::Event::Keyboard(keyboard::Event::KeyPressed {
+                    key_code,
+                    modifiers,
+                }),
+                iced::event::Status::Ignored,
+            ) = (event, status)
+            else {
+                return None;
+            };
+
+            match key_code {
+                KeyCode::Z if modifiers.control() && modifiers.shift() => Some(Msg::Redo),
+                KeyCode::Z if modifiers.control() => Some(Msg::Undo),
+                KeyCode::Y if modifiers.control() => Some(Msg::Redo),
+                _ => None,
+            }
+        });
+
+        let tick = if self.tick_watcher_count > 0 {
             iced::time::every(std::time::Duration::from_millis(50)).map(|_| Msg::Tick)
         } else {
             iced::Subscription::none()
-        }
+        };
+
+        iced::Subscription::batch([shortcuts, tick])
     }
 
     fn theme(&self) -> Self::Theme {
@@ -535,6 +643,40 @@ impl Application for App {
                 Command::none()
             }
 
+            Msg::CheckCategories => {
+                self.check_categories();
+                Command::none()
+            }
+            Msg::PopFilter => {
+                if self.bookmarks.write().pop_history() {
+                    self.set_status("returned to previous bookmark filter");
+                } else {
+                    self.set_status("no previous bookmark filter");
+                }
+
+                Command::none()
+            }
+
+            Msg::Undo => {
+                let undone = self.bookmarks.write().undo();
+                self.set_status(undone.map_or_else(
+                    || "nothing to undo".into(),
+                    |description| format!("undid {description}"),
+                ));
+
+                Command::none()
+            }
+
+            Msg::Redo => {
+                let redone = self.bookmarks.write().redo();
+                self.set_status(redone.map_or_else(
+                    || "nothing to redo".into(),
+                    |description| format!("redid {description}"),
+                ));
+
+                Command::none()
+            }
+
             Msg::UpdateShownFromSteps(value) => {
                 self.bookmark_column_state.shown_from.set_value(Some(
                     self.bookmark_column_state
@@ -567,9 +709,15 @@ impl Application for App {
 
             Msg::ApplyFilter => {
                 if let Some(ref filter) = self.bookmark_column_state.filter {
-                    self.bookmarks.write().filter_in_place(|b| {
-                        filter.is_match(b.url()) || filter.is_match(b.description())
-                    });
+                    self.bookmarks
+                        .write()
+                        .filter_in_place(|b| filter.matches(b, Bookmark::QUERY_DEFAULTS));
+                } else if !self.bookmark_column_state.filter_str.is_empty() {
+                    if let Err(err) =
+                        Query::parse::<Bookmark>(&self.bookmark_column_state.filter_str)
+                    {
+                        self.set_status(format!("invalid filter, {err}"));
+                    }
                 }
 
                 Command::none()
@@ -583,7 +731,10 @@ impl Application for App {
             Msg::AddBookmarks(bookmarks) => {
                 if let Ok(mut bookmarks) = bookmarks.lock() {
                     if let Some(bookmarks) = bookmarks.take() {
-                        self.bookmarks.write().storage.extend(bookmarks);
+                        let mut storage = self.bookmarks.write();
+                        let len = storage.storage.len();
+                        storage.storage.extend(bookmarks);
+                        storage.record("load", container::Change::Truncate(len));
                     }
                 }
 
@@ -596,6 +747,18 @@ impl Application for App {
                 Command::none()
             }
 
+            Msg::SetRecursiveApply(val) => {
+                self.settings[setting_key::RECURSIVE_APPLY] = val;
+
+                Command::none()
+            }
+
+            Msg::SetLenientLoad(val) => {
+                self.settings[setting_key::LENIENT_LOAD] = val;
+
+                Command::none()
+            }
+
             Msg::EditBookmark(index) => {
                 self.edit_bookmark(index);
                 Command::none()
@@ -630,7 +793,15 @@ impl Application for App {
                             self.bookmarks.clone(),
                         );
                     }
-                };
+                    Metric::CategoryCoverage => {
+                        self.increment_tick_watchers(1);
+                        Metrics::gather_category_coverage(
+                            self.channel.0.clone(),
+                            self.bookmarks.clone(),
+                            self.categories.clone(),
+                        );
+                    }
+                }
                 Command::none()
             }
             Msg::CloseLogPane(pane) => {
@@ -654,7 +825,7 @@ impl Application for App {
         }
     }
 
-    fn view(&self) -> iced::Element<Msg> {
+    fn view(&self) -> iced::Element<'_, Msg> {
         let bookmarks = self.bookmarks.read();
         let categories = self.categories.read();
         let infos = self.infos.read();
@@ -1,8 +1,10 @@
+mod coverage;
 mod metric;
 mod url_map;
 
 pub mod log;
 
+pub use coverage::Coverage;
 use iced::{
     theme,
     widget::{
@@ -14,7 +16,7 @@ use iced::{
 };
 pub use metric::{IntoMetricValue, Metric, Metrics, Value as MetricValue};
 use tap::Pipe;
-pub use url_map::{Tally, UrlMap};
+pub use url_map::UrlMap;
 
 use crate::Msg;
 
@@ -49,25 +51,12 @@ pub trait IterElements: Iterator {
     where
         E: Into<Element<'a, Msg>>,
         F: FnMut(Self::Item) -> E;
-
-    fn collect_row<'a, E, F>(self, f: F) -> Row<'a, Msg>
-    where
-        E: Into<Element<'a, Msg>>,
-        F: FnMut(Self::Item) -> E;
 }
 
 impl<I> IterElements for I
 where
     I: Iterator,
 {
-    fn collect_row<'a, E, F>(self, mut f: F) -> Row<'a, Msg>
-    where
-        E: Into<Element<'a, Msg>>,
-        F: FnMut(Self::Item) -> E,
-    {
-        self.fold(Row::new(), |row, item| row.push(f(item)))
-    }
-
     fn collect_coumn<'a, E, F>(self, mut f: F) -> Column<'a, Msg>
     where
         E: Into<Element<'a, Msg>>,
@@ -0,0 +1,58 @@
+use std::fmt::Display;
+
+use bookmark_library::{Category, Membership};
+
+use super::IntoMetricValue;
+
+#[derive(Clone, Debug, Default)]
+pub struct Coverage {
+    pub bookmarks: usize,
+    pub uncategorized: usize,
+    pub matches: Vec<(String, usize)>,
+    pub overlaps: Vec<(String, String, usize)>,
+}
+
+impl Coverage {
+    pub fn new(membership: &Membership, categories: &[Category]) -> Self {
+        let name = |i: usize| String::from(categories[i].name());
+
+        Self {
+            bookmarks: membership.bookmark_count(),
+            uncategorized: membership.uncategorized().count(),
+            matches: membership
+                .match_counts()
+                .into_iter()
+                .enumerate()
+                .map(|(i, count)| (name(i), count))
+                .collect(),
+            overlaps: membership
+                .overlap_counts()
+                .into_iter()
+                .map(|((first, second), count)| (name(first), name(second), count))
+                .collect(),
+        }
+    }
+}
+
+impl Display for Coverage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "uncategorized: {} of {}",
+            self.uncategorized, self.bookmarks
+        )?;
+        for (name, count) in &self.matches {
+            write!(f, ", {name}: {count}")?;
+        }
+        for (first, second, count) in &self.overlaps {
+            write!(f, ", {first} & {second}: {count}")?;
+        }
+        Ok(())
+    }
+}
+
+impl IntoMetricValue for Coverage {
+    fn into_metric_value(self) -> super::MetricValue {
+        super::MetricValue::Coverage(self)
+    }
+}
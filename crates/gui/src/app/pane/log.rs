@@ -36,26 +36,30 @@ impl State {
     }
 
     fn stat_content<'a>(app_view: View) -> Content<'a, Msg> {
-        [Metric::AverageContentStringLength, Metric::UrlOccurances]
-            .into_iter()
-            .collect_coumn(|metric| {
-                Row::new()
-                    .push(
-                        button("Gather")
-                            .on_press(Msg::GatherMetric(metric))
-                            .padding(3),
-                    )
-                    .push(text(format!(
-                        "{metric:?} [{}]",
-                        app_view.metrics.get(metric)
-                    )))
-                    .spacing(3)
-                    .align_items(Alignment::Center)
-                    .width(Length::Fill)
-            })
-            .align_items(Alignment::Start)
-            .spacing(3)
-            .pipe(scrollable_content)
+        [
+            Metric::AverageContentStringLength,
+            Metric::UrlOccurances,
+            Metric::CategoryCoverage,
+        ]
+        .into_iter()
+        .collect_coumn(|metric| {
+            Row::new()
+                .push(
+                    button("Gather")
+                        .on_press(Msg::GatherMetric(metric))
+                        .padding(3),
+                )
+                .push(text(format!(
+                    "{metric:?} [{}]",
+                    app_view.metrics.get(metric)
+                )))
+                .spacing(3)
+                .align_items(Alignment::Center)
+                .width(Length::Fill)
+        })
+        .align_items(Alignment::Start)
+        .spacing(3)
+        .pipe(scrollable_content)
     }
 
     fn url_summary_content<'a>(url_map: &UrlMap) -> Content<'a, Msg> {
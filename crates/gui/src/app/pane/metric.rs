@@ -1,15 +1,16 @@
-use bookmark_library::{shared, Bookmark};
+use bookmark_library::{category_membership, shared, Bookmark, Category};
 use conv::prelude::*;
 use std::{fmt::Display, sync::mpsc, thread};
 
 use crate::app::ChannelMessage;
 
-use super::UrlMap;
+use super::{Coverage, UrlMap};
 
 #[derive(Clone, Copy, Debug)]
 pub enum Metric {
     AverageContentStringLength,
     UrlOccurances,
+    CategoryCoverage,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -18,12 +19,14 @@ pub enum Value {
     None,
     Float(f64),
     UrlMap(UrlMap),
+    Coverage(Coverage),
 }
 
 #[derive(Clone, Default, Debug)]
 pub struct Metrics {
     average_content_string_length: Value,
     url_occurances: Value,
+    category_coverage: Value,
 }
 
 pub trait IntoMetricValue {
@@ -35,6 +38,7 @@ impl Metrics {
         match metric {
             Metric::AverageContentStringLength => &self.average_content_string_length,
             Metric::UrlOccurances => &self.url_occurances,
+            Metric::CategoryCoverage => &self.category_coverage,
         }
     }
 
@@ -42,6 +46,7 @@ impl Metrics {
         match metric {
             Metric::AverageContentStringLength => self.average_content_string_length = value,
             Metric::UrlOccurances => self.url_occurances = value,
+            Metric::CategoryCoverage => self.category_coverage = value,
         }
     }
 
@@ -102,6 +107,23 @@ impl Metrics {
             }))
         });
     }
+
+    pub fn gather_category_coverage(
+        tx: mpsc::Sender<ChannelMessage>,
+        bookmarks: shared::BufferStorage<Bookmark>,
+        categories: shared::BufferStorage<Category>,
+    ) {
+        thread::spawn(move || {
+            let bookmarks = bookmarks.read();
+            let categories = categories.read();
+            tx.send(ChannelMessage::GatheredMetric(
+                Metric::CategoryCoverage,
+                category_membership(&categories.storage, &bookmarks.storage)
+                    .map(|membership| Coverage::new(&membership, &categories.storage))
+                    .into(),
+            ))
+        });
+    }
 }
 
 impl Display for Value {
@@ -110,6 +132,7 @@ impl Display for Value {
             Value::None => write!(f, "None"),
             Value::Float(v) => write!(f, "{v}"),
             Value::UrlMap(v) => write!(f, "{:?}", v.tally()),
+            Value::Coverage(v) => write!(f, "{v}"),
         }
     }
 }
@@ -30,6 +30,12 @@ fn tool_row<'a>(app_view: View) -> Row<'a, Msg> {
                 .padding(3)
                 .style(theme::Button::Positive),
         )
+        .push(
+            button("Back")
+                .on_press(Msg::PopFilter)
+                .padding(3)
+                .style(theme::Button::Secondary),
+        )
         .push(
             button("Reset")
                 .on_press(Msg::Reset)
@@ -54,10 +60,25 @@ fn tool_row<'a>(app_view: View) -> Row<'a, Msg> {
         .spacing(3)
         .align_items(Alignment::Center);
 
+    let recursive_option = Row::new()
+        .push(text("Recursive"))
+        .push(
+            toggler(
+                None,
+                app_view.settings[setting_key::RECURSIVE_APPLY],
+                Msg::SetRecursiveApply,
+            )
+            .width(Length::Shrink),
+        )
+        .padding(0)
+        .spacing(3)
+        .align_items(Alignment::Center);
+
     Row::new()
         .push(horizontal_space(Length::Fill))
         .push(filter.width(Length::Shrink))
         .push(horizontal_space(Length::Fill))
+        .push(recursive_option.width(Length::Shrink))
         .push(edit_option.width(Length::Shrink))
         .align_items(iced::Alignment::Center)
         .spacing(3)
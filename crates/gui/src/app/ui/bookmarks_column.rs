@@ -1,6 +1,5 @@
 use crate::{setting_key, Msg, ParsedStr, View};
-use aho_corasick::AhoCorasick;
-use bookmark_library::Bookmark;
+use bookmark_library::{query::Query, Bookmark};
 use iced::{
     theme,
     widget::{button, container, horizontal_rule, horizontal_space, scrollable, text, Column, Row},
@@ -14,7 +13,7 @@ pub struct BookmarkColumnState {
     pub bookmark_scrollbar_id: scrollable::Id,
     pub desc_width: ParsedStr<usize>,
     pub url_width: ParsedStr<usize>,
-    pub filter: Option<AhoCorasick>,
+    pub filter: Option<Query>,
     pub filter_str: String,
     pub shown_bookmarks: ParsedStr<usize>,
     pub shown_from: ParsedStr<usize>,
@@ -105,9 +104,10 @@ pub fn bookmark_column<'a>(app_view: View) -> Element<'a, Msg> {
         .bookmarks
         .iter_indexed()
         .filter(|b| {
-            app_view.filter.0.map_or(true, |f| {
-                f.is_match(b.1.url()) || f.is_match(b.1.description())
-            })
+            app_view
+                .filter
+                .0
+                .is_none_or(|f| f.matches(b.1, Bookmark::QUERY_DEFAULTS))
         })
         .skip(app_view.shown_from.0)
         .take(app_view.shown_bookmarks.0)
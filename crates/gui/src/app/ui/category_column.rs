@@ -65,6 +65,12 @@ pub fn category_column<'a>(app_view: View) -> Element<'a, Msg> {
                 .style(theme::Button::Destructive)
                 .padding(3),
         )
+        .push(
+            button("Check")
+                .on_press(Msg::CheckCategories)
+                .style(theme::Button::Secondary)
+                .padding(3),
+        )
         .push(text(format!(
             "Categories ({}): ",
             app_view.category_tree.len()
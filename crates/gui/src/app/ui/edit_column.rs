@@ -1,6 +1,6 @@
 use crate::{
     app::pane::{scrollable_content, style, title_bar, IterElements},
-    Msg, View,
+    setting_key, Msg, View,
 };
 use iced::{
     theme,
@@ -120,7 +120,7 @@ impl State {
                 }
             }
             Message::ResizePane(ResizeEvent { split, ratio }) => self.panes.resize(&split, ratio),
-        };
+        }
     }
 
     pub fn view<'a>(&'a self, app_view: View) -> Element<'a, Msg> {
@@ -163,6 +163,7 @@ impl State {
 impl PaneState {
     fn settings_content<'a>(app_view: View) -> Content<'a, Msg> {
         type MsgFn = fn(String) -> Msg;
+        type ToggleFn = fn(bool) -> Msg;
         Row::new()
             .push(
                 [
@@ -199,14 +200,22 @@ impl PaneState {
                 .align_items(Alignment::End),
             )
             .push(
-                [(
-                    "Use Dark Theme",
-                    app_view
-                        .settings
-                        .check("theme", &Theme::Dark)
-                        .expect("theme should exist"),
-                    |b: bool| Msg::SetTheme(if b { Theme::Dark } else { Theme::Light }),
-                )]
+                [
+                    (
+                        "Use Dark Theme",
+                        app_view
+                            .settings
+                            .check("theme", &Theme::Dark)
+                            .expect("theme should exist"),
+                        (|b: bool| Msg::SetTheme(if b { Theme::Dark } else { Theme::Light }))
+                            as ToggleFn,
+                    ),
+                    (
+                        "Lenient Loading",
+                        app_view.settings[setting_key::LENIENT_LOAD],
+                        Msg::SetLenientLoad as ToggleFn,
+                    ),
+                ]
                 .into_iter()
                 .collect_coumn(|(title, value, msg)| {
                     Row::new()
@@ -244,8 +253,42 @@ impl PaneState {
             .pipe(scrollable_content)
     }
 
-    fn edit_category_content<'a>(_app_view: View, _category: &CategoryProxy) -> Content<'a, Msg> {
-        text("edit category").pipe(scrollable_content)
+    fn edit_category_content<'a>(pane: Pane, category: &CategoryProxy) -> Content<'a, Msg> {
+        let edit_row = |title, value: &str, change: fn(String) -> CategoryChange| {
+            Row::new()
+                .push(text(title))
+                .push(
+                    text_input("...", value, move |value| {
+                        Msg::EditColumnMessage(Message::CategoryPaneChange(CategoryPaneChange {
+                            pane,
+                            change: change(value),
+                        }))
+                    })
+                    .padding(3),
+                )
+                .spacing(3)
+                .align_items(Alignment::Center)
+        };
+
+        Column::new()
+            .push(edit_row("Name", &category.name, CategoryChange::Name))
+            .push(edit_row("Info", &category.info, CategoryChange::Info))
+            .push(text("Identifiers"))
+            .push(
+                category
+                    .identifiers
+                    .iter()
+                    .collect_coumn(|identifier| {
+                        let (kind, content) = identifier_kind(identifier);
+                        Row::new()
+                            .push(text(kind).width(Length::Units(80)))
+                            .push(text(content))
+                            .spacing(3)
+                    })
+                    .spacing(3),
+            )
+            .spacing(3)
+            .pipe(scrollable_content)
     }
 
     pub fn edit_bookmark(&mut self, BookmarkPaneChange { pane: _, change }: BookmarkPaneChange) {
@@ -275,12 +318,29 @@ impl PaneState {
                 ))
             }
 
-            PaneState::Category(category) => Self::edit_category_content(app_view, category)
-                .title_bar(title_bar(
+            PaneState::Category(category) => {
+                Self::edit_category_content(pane, category).title_bar(title_bar(
                     "Edit Category",
                     Some(Msg::EditColumnMessage(Message::ClosePane(pane))),
-                )),
+                ))
+            }
         }
         .style(style::PANE_STYLE)
     }
 }
+
+/// Get the kind of a category identifier along with its content.
+fn identifier_kind(identifier: &str) -> (&'static str, String) {
+    let kind = match identifier.get(..1) {
+        Some("[") => "require",
+        Some("(") => "include",
+        Some("<") => "whole",
+        Some("#") => "tag",
+        Some("~") => "description",
+        Some("@") => "domain",
+        Some("/") => "regex",
+        Some("!") => "exclude",
+        _ => return ("invalid", identifier.into()),
+    };
+    (kind, identifier[1..].into())
+}
@@ -1,5 +1,4 @@
-use aho_corasick::AhoCorasick;
-use bookmark_library::{container, Bookmark, Category, Info};
+use bookmark_library::{container, query::Query, Bookmark, Category, Info};
 use bookmark_settings::Settings;
 use iced::widget;
 
@@ -21,7 +20,7 @@ pub struct View<'a> {
     /// Expected max character count of bookmark descriptions deisplayed as numeric and str.
     pub desc_width: (usize, &'a str),
     /// Filter used for bookmarks as filter object and str.
-    pub filter: (Option<&'a AhoCorasick>, &'a str),
+    pub filter: (Option<&'a Query>, &'a str),
     /// Info loaded by application.
     pub infos: &'a container::BufferStorage<Info>,
     /// What is expected to fill the main area.
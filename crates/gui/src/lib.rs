@@ -34,6 +34,14 @@ pub mod setting_key {
     pub const THEME: Key<Theme> = Key::new("theme");
     /// Key for the edit mode active setting.
     pub const EDIT_MODE_ACTIVE: Key<bool> = Key::new("edit_mode_active");
+    /// Key for whether applied categories include their subcategories.
+    pub const RECURSIVE_APPLY: Key<bool> = Key::new("recursive_apply");
+    /// Key for the amount of threads used to parse loaded files.
+    pub const LOAD_THREADS: Key<usize> = Key::new("load_threads");
+    /// Key for the amount of bookmark changes that may be undone.
+    pub const JOURNAL_LIMIT: Key<usize> = Key::new("journal_limit");
+    /// Key for whether loading files quarantines malformed lines instead of failing.
+    pub const LENIENT_LOAD: Key<bool> = Key::new("lenient_load");
 }
 
 /// Enum representing what content the main area can hold.
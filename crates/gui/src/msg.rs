@@ -39,6 +39,10 @@ pub enum Msg {
     AddBookmarks(Arc<Mutex<Option<Vec<Bookmark>>>>),
     /// Enable or Disable edit mode.
     SetEditMode(bool),
+    /// Enable or Disable subcategories being included when applying categories.
+    SetRecursiveApply(bool),
+    /// Enable or Disable quarantining malformed lines when loading files.
+    SetLenientLoad(bool),
     /// Bookmark at passed index should be edited.
     EditBookmark(usize),
     /// Catgegory at passed index should be edited.
@@ -59,8 +63,16 @@ pub enum Msg {
     Debug(Arc<dyn Debug + Send + Sync>),
     /// The filter in the filter box should be filter the bookmarks until reset.
     ApplyFilter,
+    /// The bookmark filter applied last should be undone.
+    PopFilter,
+    /// The last change to the bookmarks should be undone.
+    Undo,
+    /// The last undone change to the bookmarks should be redone.
+    Redo,
     /// Any and all bookmark filters should be removed.
     Reset,
+    /// Categories should be checked for issues, which are logged.
+    CheckCategories,
     /// Misc. checks and updates should be performed.
     Tick,
     /// When a message needs to be sent but nothing should be done.
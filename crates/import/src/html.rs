@@ -1,4 +1,4 @@
-use bookmark_library::{shared, Bookmark};
+use bookmark_library::{shared, Bookmark, Timestamp};
 use scraper::{Html, Selector};
 use std::{fs::File, io};
 
@@ -23,14 +23,26 @@ pub fn build(bookmarks: shared::BufferStorage<Bookmark>) -> Box<dyn bookmark_com
         let a_selector = Selector::parse("a")
             .expect("could not create a selector for links (should never happen)");
 
+        let now = Timestamp::now();
         let mut bookmarks = bookmarks.write();
         let mut added_count = 0usize;
         for element in document.select(&a_selector) {
             let Some(url) = element.value().attr("href") else {continue;};
             let desc = element.inner_html();
-            bookmarks
-                .storage
-                .push(Bookmark::new(url, &desc, std::iter::empty::<&str>()));
+
+            // netscape bookmark files store dates as seconds
+            let date = |attr| {
+                element
+                    .value()
+                    .attr(attr)
+                    .and_then(|secs| secs.parse().ok())
+                    .and_then(Timestamp::from_secs)
+            };
+            let added = date("add_date").unwrap_or(now);
+
+            let mut bookmark = Bookmark::new_at(url, &desc, std::iter::empty::<&str>(), added);
+            bookmark.set_modified(Some(date("last_modified").unwrap_or(added)));
+            bookmarks.storage.push(bookmark);
             added_count += 1;
         }
 
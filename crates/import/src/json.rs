@@ -1,5 +1,5 @@
 use bookmark_command::CommandErr;
-use bookmark_library::{shared, Bookmark};
+use bookmark_library::{shared, Bookmark, Timestamp};
 use std::{fs::File, io::BufReader};
 
 pub fn build(bookmarks: shared::BufferStorage<Bookmark>) -> Box<dyn bookmark_command::Command> {
@@ -18,14 +18,11 @@ pub fn build(bookmarks: shared::BufferStorage<Bookmark>) -> Box<dyn bookmark_com
             .as_object()
             .ok_or_else(|| CommandErr::Execution("root of json file was not an object".into()))?;
 
+        let now = Timestamp::now();
         let mut bookmarks = bookmarks.write();
         let mut element_stack = vec![root];
         let mut added_count = 0usize;
-        while !element_stack.is_empty() {
-            let top = element_stack
-                .pop()
-                .expect("somehow failed to pop an element from a non-empty stack");
-
+        while let Some(top) = element_stack.pop() {
             if let Some(children) = top.get("children") {
                 let Some(children) = children.as_array() else {
                     println!("children member not an array\n{children:#?}");
@@ -47,9 +44,18 @@ pub fn build(bookmarks: shared::BufferStorage<Bookmark>) -> Box<dyn bookmark_com
                 let Some(url) =
                     top.get("uri").and_then(serde_json::Value::as_str) else {continue;};
 
-                bookmarks
-                    .storage
-                    .push(Bookmark::new(url, description, std::iter::empty::<&str>()));
+                // firefox backups store dates as microseconds
+                let date = |key| {
+                    top.get(key)
+                        .and_then(serde_json::Value::as_u64)
+                        .and_then(|micros| Timestamp::from_secs(micros / 1_000_000))
+                };
+                let added = date("dateAdded").unwrap_or(now);
+
+                let mut bookmark =
+                    Bookmark::new_at(url, description, std::iter::empty::<&str>(), added);
+                bookmark.set_modified(Some(date("lastModified").unwrap_or(added)));
+                bookmarks.storage.push(bookmark);
                 added_count += 1;
             }
         }
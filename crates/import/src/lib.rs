@@ -15,7 +15,10 @@ mod html;
 mod json;
 mod onetab;
 
-use bookmark_library::{command_map::Builder as CommandMapBuilder, shared::BufferStorage};
+use bookmark_library::{
+    command_map::{journaled, Builder as CommandMapBuilder, Scope},
+    shared::BufferStorage,
+};
 
 /// Type used to build import command.
 #[derive(Debug, Clone, Copy)]
@@ -31,7 +34,7 @@ impl bookmark_library::CommandFactory for Import {
         _categories: BufferStorage<bookmark_library::Category>,
         _infos: BufferStorage<bookmark_library::Info>,
     ) -> Box<dyn bookmark_command::Command> {
-        Box::new(
+        let map = Box::new(
             CommandMapBuilder::new()
                 .name("import".into())
                 .push(
@@ -47,9 +50,11 @@ impl bookmark_library::CommandFactory for Import {
                 .push(
                     "json",
                     Some("parse firefox bookmark backup"),
-                    json::build(bookmarks),
+                    json::build(bookmarks.clone()),
                 )
                 .build(),
-        )
+        );
+
+        journaled("import", Scope::Appended, map, bookmarks)
     }
 }
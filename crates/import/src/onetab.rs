@@ -1,4 +1,4 @@
-use bookmark_library::{shared, Bookmark};
+use bookmark_library::{shared, Bookmark, Timestamp};
 use std::{
     fs::File,
     io::{prelude::*, BufReader},
@@ -14,6 +14,7 @@ pub fn build(bookmarks: shared::BufferStorage<Bookmark>) -> Box<dyn bookmark_com
 
         let reader = BufReader::new(File::open(&args[0])?);
 
+        let now = Timestamp::now();
         let mut bookmarks = bookmarks.write();
         let mut added_count = 0usize;
         for line in reader.lines() {
@@ -26,7 +27,7 @@ pub fn build(bookmarks: shared::BufferStorage<Bookmark>) -> Box<dyn bookmark_com
 
             bookmarks
                 .storage
-                .push(Bookmark::new(url, desc, std::iter::empty::<&str>()));
+                .push(Bookmark::new_at(url, desc, std::iter::empty::<&str>(), now));
 
             added_count += 1;
         }
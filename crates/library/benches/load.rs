@@ -0,0 +1,82 @@
+//! Compare loading bookmarks line by line with loading them from one shared buffer, on one or
+//! multiple threads.
+//!
+//! Run with `cargo bench -p bookmark-library`, the amount of bookmarks may be given as an
+//! argument.
+
+use bookmark_library::{format, token, Bookmark};
+use bookmark_storage::{Collector, Section};
+use std::{
+    hint::black_box,
+    io::{BufRead, Write},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+const RUNS: u32 = 5;
+
+fn content(count: usize) -> String {
+    let mut content = Vec::new();
+    bookmark_storage::write_header(&mut content, format::VERSION).expect("write to vec");
+    writeln!(content, "{}", Bookmark::TOKEN_BEGIN).expect("write to vec");
+    for i in 0..count {
+        writeln!(
+            content,
+            "{} https://example.com/{i}/page {} Page number {i} {} tag{}<,>bench",
+            token::unsorted::URL,
+            token::unsorted::INFO,
+            token::unsorted::TAG,
+            i % 100,
+        )
+        .expect("write to vec");
+    }
+    writeln!(content, "{}", Bookmark::TOKEN_END).expect("write to vec");
+    String::from_utf8(content).expect("content should be utf-8")
+}
+
+fn time(name: &str, count: usize, mut run: impl FnMut() -> usize) {
+    let mut total = Duration::ZERO;
+    for _ in 0..RUNS {
+        let before = Instant::now();
+        let loaded = black_box(run());
+        total += before.elapsed();
+        assert_eq!(loaded, count);
+    }
+    println!(
+        "{name:>10}: {:>10.3} ms per load of {count} bookmarks",
+        total.as_secs_f64() * 1000.0 / f64::from(RUNS)
+    );
+}
+
+fn main() {
+    let count = std::env::args()
+        .skip(1)
+        .find_map(|arg| arg.parse().ok())
+        .unwrap_or(100_000);
+    let content = content(count);
+
+    time("load_from", count, || {
+        let lines = format::MIGRATIONS
+            .upgrade(content.as_bytes().lines().enumerate())
+            .expect("header should be valid");
+        bookmark_storage::load_from::<Bookmark>(lines)
+            .expect("content should parse")
+            .len()
+    });
+
+    let threads = std::thread::available_parallelism().map_or(1, usize::from);
+
+    for (name, threads) in [("shared", 1), ("parallel", threads)] {
+        time(name, count, || {
+            let content = Arc::<str>::from(content.as_str());
+            let mut bookmarks = Collector::<Bookmark>::new(false).with_threads(threads);
+            bookmark_storage::scan_shared(&content, &format::MIGRATIONS, &mut [&mut bookmarks])
+                .expect("content should parse");
+            bookmarks
+                .into_report()
+                .expect("content should parse")
+                .items
+                .len()
+        });
+    }
+}
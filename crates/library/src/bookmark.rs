@@ -1,9 +1,14 @@
-use crate::token;
+use crate::{container::Identify, token, Timestamp};
 use bookmark_storage::{ContentString, Field, ListField, Section, Storeable};
+use std::{collections::HashSet, fmt::Display};
+use uuid::Uuid;
 
 /// Type representing a bookmark.
 /// Easily responsible for the most important data.
-#[derive(Debug, Storeable, Clone, Default)]
+///
+/// Every bookmark has a unique id, bookmarks stored without one are given a new one when parsed
+/// which is kept once they are saved.
+#[derive(Debug, Storeable, Clone)]
 pub struct Bookmark {
     #[line]
     line: ContentString,
@@ -20,13 +25,130 @@ pub struct Bookmark {
     #[composite(tag)]
     #[token(token::unsorted::TAG)]
     tags: ListField,
+
+    #[typed]
+    #[optional]
+    #[token(token::unsorted::ADDED)]
+    added: Option<Timestamp>,
+
+    #[typed]
+    #[optional]
+    #[token(token::unsorted::MODIFIED)]
+    modified: Option<Timestamp>,
+
+    #[typed]
+    #[optional]
+    #[token(token::unsorted::VISITED)]
+    visited: Option<Timestamp>,
+
+    #[typed]
+    #[default_value(Uuid::new_v4())]
+    #[token(token::unsorted::ID)]
+    id: Uuid,
 }
 
 impl Bookmark {
+    /// Properties matched by terms of a [`Query`](crate::query::Query) not naming a property.
+    pub const QUERY_DEFAULTS: &'static [&'static str] = &["url", "description"];
+
+    /// Names of the properties holding timestamps.
+    pub const TIMESTAMPS: [&'static str; 3] = ["added", "modified", "visited"];
+
+    /// Create a new bookmark added and modified at the given time.
+    #[must_use]
+    pub fn new_at<'a>(
+        url: &str,
+        description: &str,
+        tags: impl 'a + Iterator<Item = impl AsRef<str>>,
+        at: Timestamp,
+    ) -> Self {
+        let mut bookmark = Self::new(url, description, tags);
+        bookmark.set_added(Some(at)).set_modified(Some(at));
+        bookmark
+    }
+
+    /// Get a timestamp of the bookmark by property name.
+    #[must_use]
+    pub fn timestamp(&self, property: &str) -> Option<Timestamp> {
+        match property {
+            "added" => self.added,
+            "modified" => self.modified,
+            "visited" => self.visited,
+            _ => None,
+        }
+    }
+
     /// Get the amount of bytes used to store text used by bookmark.
     #[must_use]
     pub fn stored_length(&self) -> usize {
-        self.line.as_bytes().len()
+        self.line.len()
+    }
+}
+
+impl Bookmark {
+    /// Give bookmarks at or after `from` sharing an id with an earlier bookmark a new id, such
+    /// that ids stay unique after loading files with copied ids.
+    pub fn reassign_duplicate_ids(bookmarks: &mut [Bookmark], from: usize) -> Vec<ReassignedId> {
+        let mut ids = bookmarks
+            .iter()
+            .take(from)
+            .map(Bookmark::id)
+            .collect::<HashSet<_>>();
+
+        let mut reassigned = Vec::new();
+        for (index, bookmark) in bookmarks.iter_mut().enumerate().skip(from) {
+            if ids.insert(bookmark.id) {
+                continue;
+            }
+
+            let id = Uuid::new_v4();
+            reassigned.push(ReassignedId {
+                index,
+                previous: bookmark.id,
+                id,
+            });
+            bookmark.set_id(id);
+            ids.insert(id);
+        }
+
+        reassigned
+    }
+}
+
+impl Default for Bookmark {
+    /// An empty bookmark with a new id.
+    fn default() -> Self {
+        Self {
+            line: ContentString::default(),
+            url: Field::default(),
+            description: Field::default(),
+            tags: ListField::default(),
+            added: None,
+            modified: None,
+            visited: None,
+            id: Uuid::new_v4(),
+        }
+    }
+}
+
+/// A bookmark given a new id since an earlier bookmark had the same id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReassignedId {
+    /// Index of the bookmark.
+    pub index: usize,
+    /// The id shared with an earlier bookmark.
+    pub previous: Uuid,
+    /// The new id of the bookmark.
+    pub id: Uuid,
+}
+
+impl Display for ReassignedId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "bookmark {} reuses id <{}>, it has been given the id <{}>",
+            self.index, self.previous, self.id
+        )
     }
 }
 
@@ -41,3 +163,172 @@ impl Section for Bookmark {
     const TOKEN_END: &'static str = token::unsorted::END;
     const TOKEN_BEGIN: &'static str = token::unsorted::BEGIN;
 }
+
+impl Identify for Bookmark {
+    type Id = Uuid;
+
+    fn parse_id(reference: &str) -> Option<Self::Id> {
+        Uuid::parse_str(reference).ok()
+    }
+
+    fn has_id(&self, id: &Self::Id) -> bool {
+        self.id == *id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::container::BufferStorage;
+    use bookmark_storage::{Location, ParseErr, COMPACT_MIN_WASTE};
+
+    #[test]
+    pub fn parse_errors() {
+        let Err(ParseErr::MissingToken { location, expected }) =
+            Bookmark::from_string("<url> a <info> b", Some(4))
+        else {
+            panic!("a missing tag token should be reported");
+        };
+        assert_eq!(location, Location::new(Some(4), Some(16)));
+        assert_eq!(expected, token::unsorted::TAG);
+
+        let Err(ParseErr::UnexpectedToken {
+            location,
+            expected,
+            found,
+        }) = Bookmark::from_string("<url> a <tag> b <info> c", Some(2))
+        else {
+            panic!("an out of order token should be reported");
+        };
+        assert_eq!(location, Location::new(Some(2), Some(8)));
+        assert_eq!(expected, token::unsorted::INFO);
+        assert_eq!(found, token::unsorted::TAG);
+
+        let err = Bookmark::from_string("<info> b", None)
+            .expect_err("line should not parse")
+            .with_path("bookmarks.txt");
+        assert_eq!(
+            err.location().and_then(|l| l.path.as_deref()),
+            Some("bookmarks.txt".as_ref())
+        );
+    }
+
+    #[test]
+    pub fn lenient_load() {
+        let content = format!(
+            "{}\n<url> a <info> b <tag> c\n<url> broken\n<url> d <info> e <tag> f\n{}\n",
+            token::unsorted::BEGIN,
+            token::unsorted::END,
+        );
+
+        bookmark_storage::load::<Bookmark>(&mut content.as_bytes())
+            .expect_err("strict load should fail on the broken line");
+
+        let report = bookmark_storage::load_lenient::<Bookmark>(&mut content.as_bytes())
+            .expect("lenient load should not fail")
+            .with_path("bookmarks.txt");
+
+        assert_eq!(report.items.len(), 2);
+        assert_eq!(report.items[1].url(), "d");
+        assert_eq!(report.quarantined.len(), 1);
+        assert_eq!(report.quarantined[0].line_num, 2);
+        assert_eq!(report.quarantined[0].line, "<url> broken");
+        assert_eq!(
+            report.quarantined[0].error.location(),
+            Some(&Location::new(Some(2), Some(12)).with_path("bookmarks.txt"))
+        );
+    }
+
+    #[test]
+    pub fn compaction() {
+        let mut bookmark =
+            Bookmark::from_string("<url> a <info> b <tag> c<,>d", None).expect("line should parse");
+        let line = bookmark.to_line();
+        assert!(!bookmark.is_edited());
+
+        bookmark.compact();
+        assert_eq!(bookmark.waste(), 0);
+        assert_eq!(bookmark.stored_len(), 4);
+        assert_eq!(bookmark.to_line(), line);
+        assert!(!bookmark.is_edited());
+
+        let description = "e".repeat(64);
+        for _ in 0..64 {
+            bookmark.set_description(&description);
+        }
+        assert!(bookmark.is_edited());
+        assert!(bookmark.stored_len() <= 2 * (COMPACT_MIN_WASTE + description.len() + 3));
+        assert_eq!(bookmark.description(), description);
+        assert_eq!(bookmark.url(), "a");
+        assert_eq!(bookmark.tags().collect::<Vec<_>>(), ["c", "d"]);
+    }
+
+    #[test]
+    pub fn timestamps() {
+        let old =
+            Bookmark::from_string("<url> a <info> b <tag> c", None).expect("line should parse");
+        assert_eq!(old.added(), None);
+        assert_eq!(old.timestamp("visited"), None);
+
+        let at = "2026-10-18"
+            .parse::<Timestamp>()
+            .expect("date should parse");
+        let mut bookmark = Bookmark::new_at("a", "b", ["c"].into_iter(), at);
+        bookmark.set_visited(Timestamp::from_secs(at.secs() + 60));
+
+        let line = bookmark.to_line();
+        assert_eq!(
+            line,
+            format!(
+                "<url> a <info> b <tag> c <added> 2026-10-18T00:00:00Z \
+                 <modified> 2026-10-18T00:00:00Z <visited> 2026-10-18T00:01:00Z <id> {}",
+                bookmark.id()
+            )
+        );
+
+        let parsed = Bookmark::from_string(line, None).expect("line should parse");
+        assert_eq!(parsed.modified(), Some(at));
+        assert_eq!(parsed.timestamp("visited"), bookmark.visited());
+    }
+
+    #[test]
+    pub fn ids() {
+        let line = "<url> a <info> b <tag> c";
+        let first = Bookmark::from_string(line, None).expect("line should parse");
+        let second = Bookmark::from_string(line, None).expect("line should parse");
+        assert_ne!(first.id(), second.id());
+
+        let reloaded = Bookmark::from_string(first.to_line(), None).expect("line should parse");
+        assert_eq!(reloaded.id(), first.id());
+
+        let mut bookmarks = BufferStorage::default();
+        bookmarks.storage.extend([first.clone(), second.clone()]);
+        assert_eq!(bookmarks.index_of("1"), Some(1));
+        assert_eq!(bookmarks.index_of("2"), None);
+        assert_eq!(bookmarks.index_of(&second.id().to_string()), Some(1));
+        assert_eq!(bookmarks.index_of(&Uuid::new_v4().to_string()), None);
+
+        assert_ne!(Bookmark::default().id(), Bookmark::default().id());
+    }
+
+    #[test]
+    pub fn duplicate_ids() {
+        let bookmark = Bookmark::new("a", "b", std::iter::empty::<&str>());
+        let mut bookmarks = vec![bookmark.clone(); 4];
+
+        let reassigned = Bookmark::reassign_duplicate_ids(&mut bookmarks, 2);
+
+        assert_eq!(bookmarks[0].id(), bookmark.id());
+        assert_eq!(bookmarks[1].id(), bookmark.id());
+        assert_ne!(bookmarks[2].id(), bookmark.id());
+        assert_ne!(bookmarks[3].id(), bookmark.id());
+        assert_ne!(bookmarks[2].id(), bookmarks[3].id());
+        assert_eq!(
+            reassigned
+                .iter()
+                .map(|r| (r.index, r.id))
+                .collect::<Vec<_>>(),
+            [(2, bookmarks[2].id()), (3, bookmarks[3].id())]
+        );
+    }
+}
@@ -1,6 +1,10 @@
-use crate::{container::BufferStorage, token, Bookmark};
+use crate::{
+    container::{BufferStorage, Identify},
+    token, url, Bookmark,
+};
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder};
 use bookmark_storage::{ContentString, Field, ListField, Section, Storeable};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use thiserror::Error;
 
 /// Type representing a category.
@@ -11,6 +15,10 @@ use thiserror::Error;
 /// The conditions can be both required, meaning all bookmarks in the category must fullfill them,
 /// inclusive, meaning the bookmark may include it or another inclusive requirement, or meaning the
 /// requirement is a perfect match with the bookmark.
+///
+/// Inclusive requirements may also test other parts of the bookmark, `#` a tag, `~` a substring of
+/// the description, `@` the domain of the url and `/` a regex matching either url or description.
+/// Bookmarks with a url containing any exclusion `!` are never in the category.
 #[derive(Debug, Storeable, Default, Clone)]
 pub struct Category {
     #[line]
@@ -58,9 +66,19 @@ pub struct IdentifierContainer<'a> {
     pub whole: Vec<&'a str>,
     /// The optional requirements.
     pub include: Vec<&'a str>,
+    /// Tags the bookmark may have.
+    pub tag: Vec<&'a str>,
+    /// Substrings the description may contain.
+    pub description: Vec<&'a str>,
+    /// Domains the url may have.
+    pub domain: Vec<&'a str>,
+    /// Regular expressions the url or description may match.
+    pub regex: Vec<&'a str>,
+    /// The excluding requirements.
+    pub exclude: Vec<&'a str>,
 }
 
-impl<'a> IdentifierContainer<'a> {
+impl IdentifierContainer<'_> {
     /// Tally how many of each kind of requirement exist.
     #[must_use]
     pub fn tally(&self) -> HashMap<char, usize> {
@@ -68,8 +86,85 @@ impl<'a> IdentifierContainer<'a> {
             ('(', self.include.len()),
             ('[', self.require.len()),
             ('<', self.whole.len()),
+            ('#', self.tag.len()),
+            ('~', self.description.len()),
+            ('@', self.domain.len()),
+            ('/', self.regex.len()),
+            ('!', self.exclude.len()),
         ])
     }
+
+    /// Compile the requirements into a [`CategoryMatcher`].
+    ///
+    /// # Errors
+    /// If one of the regular expressions is invalid.
+    pub fn matcher(&self) -> Result<CategoryMatcher, IdentifierErr> {
+        let case_insensitive = |patterns: &[&str]| {
+            AhoCorasickBuilder::new()
+                .ascii_case_insensitive(true)
+                .auto_configure(patterns)
+                .build(patterns)
+        };
+        let owned = |patterns: &[&str]| patterns.iter().copied().map(String::from).collect();
+
+        Ok(CategoryMatcher {
+            require: owned(&self.require),
+            whole: owned(&self.whole),
+            include: case_insensitive(&self.include),
+            tag: owned(&self.tag),
+            description: case_insensitive(&self.description),
+            domain: owned(&self.domain),
+            exclude: case_insensitive(&self.exclude),
+            regex: self
+                .regex
+                .iter()
+                .map(|pattern| {
+                    regex::Regex::new(pattern)
+                        .map_err(|err| IdentifierErr(format!("invalid regex /{pattern}/, {err}")))
+                })
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// Compiled requirements of a category, used to check whether bookmarks belong to it.
+#[derive(Clone, Debug)]
+pub struct CategoryMatcher {
+    require: Vec<String>,
+    whole: Vec<String>,
+    include: AhoCorasick,
+    tag: Vec<String>,
+    description: AhoCorasick,
+    domain: Vec<String>,
+    regex: Vec<regex::Regex>,
+    exclude: AhoCorasick,
+}
+
+impl CategoryMatcher {
+    /// Check whether a bookmark fulfills all required requirements and any inclusive requirement,
+    /// without fulfilling any excluding requirement.
+    #[must_use]
+    pub fn is_match(&self, bookmark: &Bookmark) -> bool {
+        let url = bookmark.url();
+        let description = bookmark.description();
+
+        !self.exclude.is_match(url)
+            && self.require.iter().all(|r| url.contains(r.as_str()))
+            && (self.whole.iter().any(|w| w == url)
+                || self.include.is_match(url)
+                || bookmark
+                    .tags()
+                    .any(|tag| self.tag.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+                || self.description.is_match(description)
+                || (!self.domain.is_empty() && {
+                    let domain = url::domain(url);
+                    self.domain.iter().any(|d| d.eq_ignore_ascii_case(domain))
+                })
+                || self
+                    .regex
+                    .iter()
+                    .any(|re| re.is_match(url) || re.is_match(description)))
+    }
 }
 
 impl Category {
@@ -100,6 +195,21 @@ impl Category {
                 "[" => {
                     identifier_container.require.push(identifier_content);
                 }
+                "#" => {
+                    identifier_container.tag.push(identifier_content);
+                }
+                "~" => {
+                    identifier_container.description.push(identifier_content);
+                }
+                "@" => {
+                    identifier_container.domain.push(identifier_content);
+                }
+                "/" => {
+                    identifier_container.regex.push(identifier_content);
+                }
+                "!" => {
+                    identifier_container.exclude.push(identifier_content);
+                }
                 spec => {
                     return Err(IdentifierErr(format!(
                         "invalid identifier specifier '{spec}' in identifier: {identifier}"
@@ -111,23 +221,72 @@ impl Category {
         Ok(identifier_container)
     }
 
+    /// Get the requirements of a category compiled into a [`CategoryMatcher`].
+    ///
+    /// # Errors
+    /// If one of the requirements is malformed.
+    pub fn matcher(&self) -> Result<CategoryMatcher, IdentifierErr> {
+        self.identifier_container()?.matcher()
+    }
+
     /// Apply the category to a [`BufferStorage`] of bookmarks.
     ///
     /// # Errors
     /// If the Category cirteria are malformed.
     pub fn apply(&self, bookmarks: &mut BufferStorage<Bookmark>) -> Result<(), IdentifierErr> {
-        let criteria = self.identifier_container()?;
+        let matcher = self.matcher()?;
 
-        let include_matcher = aho_corasick::AhoCorasickBuilder::new()
-            .ascii_case_insensitive(true)
-            .auto_configure(&criteria.include)
-            .build(&criteria.include);
+        bookmarks.filter_in_place(|bookmark| matcher.is_match(bookmark));
 
-        bookmarks.filter_in_place(|bookmark| {
-            criteria.require.iter().all(|r| bookmark.url().contains(r))
-                && (criteria.whole.iter().any(|w| *w == bookmark.url())
-                    || include_matcher.is_match(bookmark.url()))
-        });
+        Ok(())
+    }
+
+    /// Get the category followed by every category reachable through subcategories, each
+    /// category is given once such that cycles are not followed. Subcategories without a matching
+    /// category among the given ones are skipped.
+    #[must_use]
+    pub fn with_descendants<'a>(&'a self, categories: &'a [Category]) -> Vec<&'a Category> {
+        let by_id = categories
+            .iter()
+            .map(|category| (category.id(), category))
+            .collect::<HashMap<_, _>>();
+
+        let mut visited = HashSet::from([self.id()]);
+        let mut found = vec![self];
+        let mut stack = self.subcategories().rev().collect::<Vec<_>>();
+
+        while let Some(id) = stack.pop() {
+            let Some(category) = by_id.get(id) else {
+                continue;
+            };
+            if !visited.insert(id) {
+                continue;
+            }
+
+            found.push(category);
+            stack.extend(category.subcategories().rev());
+        }
+
+        found
+    }
+
+    /// Apply the category to a [`BufferStorage`] of bookmarks, keeping bookmarks matching the
+    /// category or any of its descendants among the given categories.
+    ///
+    /// # Errors
+    /// If the criteria of the category or any of its descendants are malformed.
+    pub fn apply_recursive(
+        &self,
+        categories: &[Category],
+        bookmarks: &mut BufferStorage<Bookmark>,
+    ) -> Result<(), IdentifierErr> {
+        let matchers = self
+            .with_descendants(categories)
+            .into_iter()
+            .map(Category::matcher)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        bookmarks.filter_in_place(|bookmark| matchers.iter().any(|m| m.is_match(bookmark)));
 
         Ok(())
     }
@@ -138,3 +297,142 @@ impl Section for Category {
     const TOKEN_END: &'static str = token::category::END;
     const TOKEN_BEGIN: &'static str = token::category::BEGIN;
 }
+
+impl Identify for Category {
+    type Id = String;
+
+    fn parse_id(reference: &str) -> Option<Self::Id> {
+        Some(reference.into())
+    }
+
+    fn has_id(&self, id: &Self::Id) -> bool {
+        self.id() == id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn identifier_kinds() {
+        let bookmarks = [
+            Bookmark::new(
+                "https://arxiv.org/abs/1",
+                "Attention",
+                ["paper"].into_iter(),
+            ),
+            Bookmark::new(
+                "https://www.rfc-editor.org/rfc/rfc9110",
+                "RFC 9110 HTTP",
+                std::iter::empty::<&str>(),
+            ),
+            Bookmark::new("https://docs.rs/regex", "Regex Docs", ["rust"].into_iter()),
+            Bookmark::new(
+                "https://example.com/misc",
+                "misc",
+                std::iter::empty::<&str>(),
+            ),
+        ];
+        let matching = |identifiers: &[&str]| {
+            let category = Category::new(
+                "id",
+                "name",
+                "",
+                identifiers.iter(),
+                std::iter::empty::<&str>(),
+            );
+            let matcher = category.matcher().expect("identifiers should be valid");
+            bookmarks
+                .iter()
+                .enumerate()
+                .filter_map(|(i, bookmark)| matcher.is_match(bookmark).then_some(i))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(matching(&["#Paper"]), [0]);
+        assert_eq!(matching(&["~docs"]), [2]);
+        assert_eq!(matching(&["@rfc-editor.org"]), [1]);
+        assert_eq!(matching(&[r"/RFC \d+"]), [1]);
+        assert_eq!(matching(&["#rust", "(example", "[https"]), [2, 3]);
+        assert!(matching(&[]).is_empty());
+        assert_eq!(matching(&["[https", "(.org", "(docs", "!Arxiv"]), [1, 2]);
+        assert_eq!(matching(&["#paper", "!arxiv.org"]), []);
+
+        let invalid = Category::new("id", "name", "", ["/("].iter(), std::iter::empty::<&str>());
+        assert!(invalid.identifier_container().is_ok());
+        assert!(invalid.matcher().is_err());
+        assert_eq!(
+            invalid.identifier_container().map(|c| c.tally()[&'/']).ok(),
+            Some(1)
+        );
+    }
+
+    #[test]
+    pub fn recursive_apply() {
+        let category = |id: &str, identifier: &str, subcategories: &[&str]| {
+            Category::new(id, id, "", [identifier].into_iter(), subcategories.iter())
+        };
+        let categories = [
+            category("programming", "(programming", &["rust", "missing"]),
+            category("rust", "#rust", &["async"]),
+            category("async", "~async", &["programming", "rust"]),
+        ];
+
+        let ids = |index: usize| {
+            categories[index]
+                .with_descendants(&categories)
+                .into_iter()
+                .map(Category::id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(0), ["programming", "rust", "async"]);
+        assert_eq!(ids(2), ["async", "programming", "rust"]);
+
+        let mut bookmarks = BufferStorage::default();
+        bookmarks.storage.extend([
+            Bookmark::new("https://a.org", "async io", std::iter::empty::<&str>()),
+            Bookmark::new("https://b.org", "", ["rust"].into_iter()),
+            Bookmark::new("https://c.org", "", std::iter::empty::<&str>()),
+        ]);
+
+        categories[1]
+            .apply_recursive(&categories, &mut bookmarks)
+            .expect("identifiers should be valid");
+        assert_eq!(
+            bookmarks.iter_indexed().map(|(i, _)| i).collect::<Vec<_>>(),
+            [0, 1]
+        );
+
+        bookmarks.reset();
+        categories[1]
+            .apply(&mut bookmarks)
+            .expect("identifiers should be valid");
+        assert_eq!(
+            bookmarks.iter_indexed().map(|(i, _)| i).collect::<Vec<_>>(),
+            [1]
+        );
+    }
+
+    #[test]
+    pub fn numeric_ids() {
+        let category = |id: &str| {
+            Category::new(
+                id,
+                id,
+                "",
+                std::iter::empty::<&str>(),
+                std::iter::empty::<&str>(),
+            )
+        };
+        let mut categories = BufferStorage::default();
+        categories
+            .storage
+            .extend([category("2024"), category("1"), category("0")]);
+
+        assert_eq!(categories.index_of("2024"), Some(0));
+        assert_eq!(categories.index_of("1"), Some(1));
+        assert_eq!(categories.index_of("0"), Some(0));
+        assert_eq!(categories.index_of("3"), None);
+    }
+}
@@ -0,0 +1,270 @@
+use crate::{category::Category, info::Info, Graph, IdentifierErr};
+use std::{collections::HashMap, fmt::Display};
+
+/// Problem found in the hierarchy or identifiers of categories.
+#[derive(Debug, Clone)]
+pub enum CategoryIssue {
+    /// Categories that are subcategories of each other, directly or indirectly.
+    Cycle(Vec<String>),
+    /// A subcategory id without a matching category.
+    Dangling {
+        /// Id of the category with the subcategory.
+        category: String,
+        /// The subcategory id.
+        subcategory: String,
+    },
+    /// A top level category id in an [`Info`] without a matching category.
+    MissingRoot(String),
+    /// A category that cannot be reached from any top level category.
+    Unreachable(String),
+    /// An id used by more than one category.
+    DuplicateId {
+        /// The id.
+        id: String,
+        /// Indices of the categories using the id.
+        indices: Vec<usize>,
+    },
+    /// A category with identifiers that cannot be used.
+    Malformed {
+        /// Id of the category.
+        category: String,
+        /// What is wrong with the identifiers.
+        err: IdentifierErr,
+    },
+}
+
+impl Display for CategoryIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cycle(ids) => write!(f, "cycle between categories <{}>", ids.join(">, <")),
+            Self::Dangling {
+                category,
+                subcategory,
+            } => write!(
+                f,
+                "category <{category}> has subcategory <{subcategory}> which does not exist"
+            ),
+            Self::MissingRoot(id) => {
+                write!(f, "top level category <{id}> does not exist")
+            }
+            Self::Unreachable(id) => write!(
+                f,
+                "category <{id}> cannot be reached from any top level category"
+            ),
+            Self::DuplicateId { id, indices } => {
+                write!(f, "id <{id}> is used by categories at indices {indices:?}")
+            }
+            Self::Malformed { category, err } => {
+                write!(f, "category <{category}> has malformed identifiers, {err}")
+            }
+        }
+    }
+}
+
+/// Check categories for cycles, dangling subcategories, categories not reachable from the top
+/// level categories of infos, duplicate ids and malformed identifiers.
+#[must_use]
+pub fn check_categories(categories: &[Category], infos: &[Info]) -> Vec<CategoryIssue> {
+    let mut issues = Vec::new();
+
+    let mut by_id = HashMap::<&str, Vec<usize>>::new();
+    for (i, category) in categories.iter().enumerate() {
+        by_id.entry(category.id()).or_default().push(i);
+    }
+
+    let mut duplicates = by_id
+        .iter()
+        .filter(|(_, indices)| indices.len() > 1)
+        .map(|(id, indices)| CategoryIssue::DuplicateId {
+            id: (*id).into(),
+            indices: indices.clone(),
+        })
+        .collect::<Vec<_>>();
+    duplicates.sort_by_key(|issue| match issue {
+        CategoryIssue::DuplicateId { indices, .. } => indices[0],
+        _ => unreachable!("only duplicate ids are sorted"),
+    });
+    issues.extend(duplicates);
+
+    for category in categories {
+        for subcategory in category.subcategories() {
+            if !by_id.contains_key(subcategory) {
+                issues.push(CategoryIssue::Dangling {
+                    category: category.id().into(),
+                    subcategory: subcategory.into(),
+                });
+            }
+        }
+    }
+
+    let mut roots = Vec::new();
+    for id in infos.iter().flat_map(Info::categories) {
+        match by_id.get(id) {
+            Some(indices) => roots.extend(indices.iter().copied()),
+            None => issues.push(CategoryIssue::MissingRoot(id.into())),
+        }
+    }
+
+    let graph = Graph::from(categories);
+    issues.extend(cycles(&graph).into_iter().map(|component| {
+        CategoryIssue::Cycle(
+            component
+                .into_iter()
+                .map(|i| categories[i].id().into())
+                .collect(),
+        )
+    }));
+
+    let from_roots = graph.reachable(roots);
+    issues.extend(
+        categories
+            .iter()
+            .zip(from_roots)
+            .filter(|(_, reached)| !reached)
+            .map(|(category, _)| CategoryIssue::Unreachable(category.id().into())),
+    );
+
+    issues.extend(categories.iter().filter_map(|category| {
+        category
+            .matcher()
+            .err()
+            .map(|err| CategoryIssue::Malformed {
+                category: category.id().into(),
+                err,
+            })
+    }));
+
+    issues
+}
+
+/// Find strongly connected components with more than one node or a self loop using an iterative
+/// Tarjan pass, each component is sorted and components are ordered by their first node.
+fn cycles(graph: &Graph) -> Vec<Vec<usize>> {
+    const UNVISITED: usize = usize::MAX;
+
+    let mut index = vec![UNVISITED; graph.node_count()];
+    let mut low = vec![0; graph.node_count()];
+    let mut on_stack = vec![false; graph.node_count()];
+    let mut stack = Vec::new();
+    let mut components = Vec::new();
+    let mut next = 0;
+
+    for start in 0..graph.node_count() {
+        if index[start] != UNVISITED {
+            continue;
+        }
+
+        // Nodes being visited together with the edges left to follow.
+        let mut visiting = vec![(start, graph.edges(start))];
+        index[start] = next;
+        low[start] = next;
+        next += 1;
+        stack.push(start);
+        on_stack[start] = true;
+
+        while let Some((node, edges)) = visiting.last_mut() {
+            let node = *node;
+            if let Some(to) = edges.next() {
+                if index[to] == UNVISITED {
+                    index[to] = next;
+                    low[to] = next;
+                    next += 1;
+                    stack.push(to);
+                    on_stack[to] = true;
+                    visiting.push((to, graph.edges(to)));
+                } else if on_stack[to] {
+                    low[node] = low[node].min(index[to]);
+                }
+                continue;
+            }
+
+            visiting.pop();
+            if let Some((parent, _)) = visiting.last() {
+                low[*parent] = low[*parent].min(low[node]);
+            }
+
+            if low[node] == index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+
+                if component.len() > 1 || matches!(graph.is_edge(node, node), Ok(true)) {
+                    component.sort_unstable();
+                    components.push(component);
+                }
+            }
+        }
+    }
+
+    components.sort_unstable_by_key(|component| component[0]);
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn hierarchy_issues() {
+        let category = |id: &str, identifiers: &[&str], subcategories: &[&str]| {
+            Category::new(id, id, "", identifiers.iter(), subcategories.iter())
+        };
+        let categories = [
+            category("root", &[], &["a", "missing"]),
+            category("a", &[], &["b"]),
+            category("b", &["/("], &["a"]),
+            category("loose", &[], &["loose"]),
+            category("a", &[], &[]),
+        ];
+        let info = Info::new(["root", "gone"].into_iter(), std::iter::empty::<&str>());
+
+        let issues = check_categories(&categories, &[info])
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            issues[..5],
+            [
+                "id <a> is used by categories at indices [1, 4]",
+                "category <root> has subcategory <missing> which does not exist",
+                "top level category <gone> does not exist",
+                "cycle between categories <a>, <b>",
+                "cycle between categories <loose>",
+            ]
+        );
+        assert_eq!(
+            issues[5],
+            "category <loose> cannot be reached from any top level category"
+        );
+        assert!(issues[6].starts_with("category <b> has malformed identifiers"));
+        assert_eq!(issues.len(), 7);
+
+        let graph = Graph::from(&categories[..]);
+        assert!(graph.is_edge(0, 1).expect("nodes should exist"));
+        assert!(graph.is_edge(0, 5).is_err());
+    }
+
+    #[test]
+    pub fn strongly_connected() {
+        let graph = |node_count: usize, edges: &[(usize, usize)]| {
+            let mut graph = Graph::new(node_count);
+            for (from, to) in edges {
+                graph
+                    .set_edge(*from, *to, true)
+                    .expect("nodes should exist");
+            }
+            graph
+        };
+
+        let edges = [(0, 3), (1, 1), (2, 0), (2, 4), (3, 2), (4, 5), (6, 0)];
+        assert_eq!(cycles(&graph(7, &edges)), [vec![0, 2, 3], vec![1]]);
+        assert!(cycles(&graph(2, &[(0, 1)])).is_empty());
+        assert_eq!(graph(7, &edges).edges(2).collect::<Vec<_>>(), [0, 4]);
+    }
+}
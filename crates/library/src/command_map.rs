@@ -2,9 +2,15 @@
 //! implementing the [Command] trait.
 
 mod bookmark;
+mod bulk;
 mod category;
+mod combine;
+mod compact;
 mod count;
+mod delete;
+mod history;
 mod info;
+mod journal;
 mod list;
 mod load;
 mod print;
@@ -14,6 +20,8 @@ mod save;
 mod select;
 mod set;
 
+pub use journal::{journaled, journaled_load, Lengths, Scope};
+
 use crate::{bookmark::Bookmark, category::Category, info::Info, shared};
 use bookmark_command::{Command, CommandErr};
 use std::{cell::RefCell, collections::HashMap, fmt::Debug};
@@ -99,7 +107,7 @@ pub struct CommandMap<'a> {
     fallback: Option<String>,
 }
 
-impl<'a> CommandMap<'a> {
+impl CommandMap<'_> {
     /// Gives the name of the [`CommandMap`].
     #[must_use]
     pub fn name(&self) -> &str {
@@ -197,7 +205,12 @@ impl CommandMap<'static> {
             .push(
                 "category",
                 None,
-                category::build("category".into(), categories.clone(), bookmarks.clone()),
+                category::build(
+                    "category".into(),
+                    categories.clone(),
+                    bookmarks.clone(),
+                    infos.clone(),
+                ),
             )
             .push(
                 "bookmark",
@@ -207,12 +220,22 @@ impl CommandMap<'static> {
             .push(
                 "info",
                 None,
-                info::build("info".into(), infos.clone(), categories.clone()),
+                info::build(
+                    "info".into(),
+                    infos.clone(),
+                    categories.clone(),
+                    bookmarks.clone(),
+                ),
             )
             .push(
                 "load",
                 None,
-                load::All::build(categories.clone(), bookmarks.clone(), infos.clone()),
+                journaled_load(
+                    load::All::build(categories.clone(), bookmarks.clone(), infos.clone()),
+                    bookmarks.clone(),
+                    categories.clone(),
+                    infos.clone(),
+                ),
             )
             .push("save", None, save::All::build(infos, categories, bookmarks))
             .push(
@@ -226,11 +249,11 @@ impl CommandMap<'static> {
     }
 }
 
-impl<'a> Command for CommandMap<'a> {
+impl Command for CommandMap<'_> {
     fn call(&mut self, args: &[String]) -> Result<(), CommandErr> {
         CommandMap::call(
             self,
-            args.get(0).ok_or_else(|| {
+            args.first().ok_or_else(|| {
                 CommandErr::Execution("needs to be called with a subcommand".into())
             })?,
             &args[1..],
@@ -1,36 +1,68 @@
+pub mod date;
 pub mod filter;
 pub mod new;
 pub mod regex;
 pub mod sort;
+pub mod touch;
 pub mod unique;
 
+use bookmark_command::Command;
+
 use crate::{
     bookmark::Bookmark,
     command_map::{
-        count, list, load, print, push, save, select, set, Builder as CommandMapBuilder, CommandMap,
+        bulk, combine, compact, count, delete, history, journal, journaled, list, load, print,
+        push, save, select, set, Builder as CommandMapBuilder, Scope,
     },
     shared,
 };
 
-pub fn build(name: String, bookmarks: shared::BufferStorage<Bookmark>) -> Box<CommandMap<'static>> {
+pub fn build(name: String, bookmarks: shared::BufferStorage<Bookmark>) -> Box<dyn Command> {
+    let builder = history::push_commands(CommandMapBuilder::new().name(name), &bookmarks);
+    let builder = combine::push_operations(builder, &bookmarks);
+    let builder = journal::push_commands(builder, &bookmarks);
+    let builder = push_edit_commands(builder, &bookmarks);
+
     Box::new(
-        CommandMapBuilder::new()
-            .name(name)
+        builder
             .push("list", None, list::List::build(bookmarks.clone()))
-            .push("filter", None, filter::Filter::build(bookmarks.clone()))
+            .push(
+                "filter",
+                Some("filter bookmarks by a query such as tag:rust AND (url:github OR desc:\"async io\") AND NOT domain:reddit.com, terms without a property match url or description\nusage: filter QUERY"),
+                filter::Filter::build(bookmarks.clone()),
+            )
             .push(
                 "filter-inv",
-                None,
+                Some("filter bookmarks not matching a query\nusage: filter-inv QUERY"),
                 filter::Inverse::build(bookmarks.clone()),
             )
+            .push(
+                "date",
+                Some("filter bookmarks by a timestamp, FROM inclusive and TO exclusive, \"-\" leaves a bound open\nusage: date added|modified|visited FROM [TO]"),
+                date::Date::build(bookmarks.clone()),
+            )
             .push("regex", None, regex::Regex::build(bookmarks.clone()))
             .push("regex-inv", None, regex::Inverse::build(bookmarks.clone()))
             .push("count", None, count::Count::build(bookmarks.clone()))
-            .push("load", None, load::Load::build(bookmarks.clone()))
+            .push(
+                "load",
+                None,
+                journaled(
+                    "load",
+                    Scope::Appended,
+                    load::unique_ids(load::Load::build(bookmarks.clone()), bookmarks.clone()),
+                    bookmarks.clone(),
+                ),
+            )
+            .push(
+                "compact",
+                Some("remove content no longer used by bookmarks"),
+                compact::Compact::build(bookmarks.clone()),
+            )
             .push("save", None, save::Save::build(bookmarks.clone()))
             .push(
                 "select",
-                Some("select a bookmark\nusage: select INDEX"),
+                Some("select a bookmark by index or id\nusage: select INDEX|ID"),
                 select::Select::build(bookmarks.clone()),
             )
             .push(
@@ -38,31 +70,69 @@ pub fn build(name: String, bookmarks: shared::BufferStorage<Bookmark>) -> Box<Co
                 Some("print selected bookmark\nusage: print"),
                 print::build(bookmarks.clone()),
             )
-            .push(
-                "push",
-                Some("print selected bookmark\nusage: push FIELD [VALUE, ...]"),
-                push::build(bookmarks.clone()),
-            )
-            .push(
-                "new",
-                Some("add a new empty bookmark"),
-                new::New::build(bookmarks.clone()),
-            )
-            .push(
-                "set",
-                Some("set a value on a bookmark\nusage: set VALUE [VALUES, [...]]"),
-                set::Set::build(bookmarks.clone()),
-            )
             .push(
                 "sort",
-                Some("sort bookmarks by url"),
+                Some("sort bookmarks by one or more keys, url by default, keys are domain, tag-count or any property and may be suffixed by :asc or :desc\nusage: sort [--view] [--ignore-case] [--natural] [KEY[:asc|:desc] ...]"),
                 sort::build(bookmarks.clone()),
             )
             .push(
                 "unique",
-                Some("sort bookmarks and remove duplicates"),
+                Some("merge bookmarks with the same normalized url, keeping the longest description and all tags, or list them with --dry-run\nusage: unique [--dry-run]"),
                 unique::build(bookmarks),
             )
             .build(),
     )
 }
+
+/// Push commands editing bookmarks, each recording its changes such that they may be undone.
+fn push_edit_commands(
+    builder: CommandMapBuilder<'static>,
+    bookmarks: &shared::BufferStorage<Bookmark>,
+) -> CommandMapBuilder<'static> {
+    builder
+        .push(
+            "push",
+            Some("print selected bookmark\nusage: push FIELD [VALUE, ...]"),
+            journaled(
+                "push",
+                Scope::Selected,
+                touch::build(push::build(bookmarks.clone()), bookmarks.clone()),
+                bookmarks.clone(),
+            ),
+        )
+        .push(
+            "bulk",
+            Some("edit every item in the buffer, showing a preview to confirm unless --yes is given, replace uses a regex on every value of the property\nusage: bulk [--yes] set|push|remove PROPERTY [VALUE ...]\nusage: bulk [--yes] replace PROPERTY PATTERN REPLACEMENT\nusage: bulk confirm|cancel"),
+            bulk::build(bookmarks.clone(), touch::mark_modified),
+        )
+        .push(
+            "delete",
+            Some("delete the selected bookmark or a bookmark by index or id\nusage: delete [INDEX|ID]"),
+            delete::build(bookmarks.clone(), |_, _| None),
+        )
+        .push(
+            "delete-buffer",
+            Some("delete every bookmark in the buffer, only showing the amount unless --yes is given\nusage: delete-buffer [--yes]"),
+            delete::build_buffer(bookmarks.clone(), |_, _| None),
+        )
+        .push(
+            "new",
+            Some("add a new empty bookmark"),
+            journaled(
+                "new",
+                Scope::Appended,
+                new::New::build(bookmarks.clone()),
+                bookmarks.clone(),
+            ),
+        )
+        .push(
+            "set",
+            Some("set a value on a bookmark\nusage: set VALUE [VALUES, [...]]"),
+            journaled(
+                "set",
+                Scope::Selected,
+                touch::build(set::Set::build(bookmarks.clone()), bookmarks.clone()),
+                bookmarks.clone(),
+            ),
+        )
+}
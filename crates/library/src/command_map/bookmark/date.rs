@@ -0,0 +1,53 @@
+use crate::{bookmark::Bookmark, shared, Timestamp};
+use bookmark_command::{Command, CommandErr};
+
+#[derive(Debug, Command)]
+pub struct Date {
+    bookmarks: shared::BufferStorage<Bookmark>,
+}
+
+impl Date {
+    /// Parse a bound of the range, where "-" leaves it open.
+    fn bound(arg: Option<&String>) -> Result<Option<Timestamp>, CommandErr> {
+        match arg.map(String::as_str) {
+            None | Some("-") => Ok(None),
+            Some(arg) => arg
+                .parse()
+                .map(Some)
+                .map_err(|err| CommandErr::Usage(format!("{err}"))),
+        }
+    }
+}
+
+impl Command for Date {
+    fn call(&mut self, args: &[String]) -> Result<(), CommandErr> {
+        let [property, from, rest @ ..] = args else {
+            return Err(CommandErr::Usage(
+                "date should be called with a timestamp property and a range".into(),
+            ));
+        };
+        if rest.len() > 1 {
+            return Err(CommandErr::Usage(
+                "date should be called with at most three arguments".into(),
+            ));
+        }
+
+        if !Bookmark::TIMESTAMPS.contains(&property.as_str()) {
+            return Err(CommandErr::Usage(format!(
+                "{property} is not one of {}",
+                Bookmark::TIMESTAMPS.join(", ")
+            )));
+        }
+
+        let from = Self::bound(Some(from))?;
+        let to = Self::bound(rest.first())?;
+
+        self.bookmarks.write().filter_in_place(|bookmark| {
+            bookmark.timestamp(property).is_some_and(|timestamp| {
+                from.is_none_or(|from| from <= timestamp) && to.is_none_or(|to| timestamp < to)
+            })
+        });
+
+        Ok(())
+    }
+}
@@ -1,7 +1,28 @@
-use crate::{bookmark::Bookmark, shared};
-use aho_corasick::AhoCorasickBuilder;
+use crate::{bookmark::Bookmark, query::Query, shared};
 use bookmark_command::{Command, CommandErr};
 
+/// Filter the buffer by a query, optionally keeping the bookmarks not matching it.
+fn filter(
+    bookmarks: &shared::BufferStorage<Bookmark>,
+    args: &[String],
+    inverse: bool,
+) -> Result<(), CommandErr> {
+    if args.is_empty() {
+        return Err(CommandErr::Execution(
+            "filter needs one or more arguments".into(),
+        ));
+    }
+
+    let query =
+        Query::from_args::<Bookmark>(args).map_err(|err| CommandErr::Usage(err.to_string()))?;
+
+    bookmarks
+        .write()
+        .filter_in_place(|bookmark| query.matches(bookmark, Bookmark::QUERY_DEFAULTS) != inverse);
+
+    Ok(())
+}
+
 #[derive(Debug, Command)]
 pub struct Filter {
     bookmarks: shared::BufferStorage<Bookmark>,
@@ -9,29 +30,7 @@ pub struct Filter {
 
 impl Command for Filter {
     fn call(&mut self, args: &[String]) -> Result<(), CommandErr> {
-        if args.is_empty() {
-            return Err(CommandErr::Execution(
-                "filter needs one or more arguments".into(),
-            ));
-        }
-
-        let filters = args
-            .iter()
-            .map(|s| {
-                AhoCorasickBuilder::new()
-                    .auto_configure(&[s])
-                    .ascii_case_insensitive(true)
-                    .build([s])
-            })
-            .collect::<Vec<_>>();
-
-        self.bookmarks.write().filter_in_place(|bookmark| {
-            filters
-                .iter()
-                .all(|f| f.is_match(bookmark.url()) || f.is_match(bookmark.description()))
-        });
-
-        Ok(())
+        filter(&self.bookmarks, args, false)
     }
 }
 
@@ -42,16 +41,6 @@ pub struct Inverse {
 
 impl Command for Inverse {
     fn call(&mut self, args: &[String]) -> Result<(), CommandErr> {
-        if args.is_empty() {
-            return Err(CommandErr::Execution(
-                "filter needs one or more arguments".into(),
-            ));
-        }
-
-        self.bookmarks
-            .write()
-            .filter_in_place(|bookmark| !args.iter().any(|arg| bookmark.url().contains(arg)));
-
-        Ok(())
+        filter(&self.bookmarks, args, true)
     }
 }
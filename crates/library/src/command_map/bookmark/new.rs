@@ -1,4 +1,4 @@
-use crate::{bookmark::Bookmark, shared};
+use crate::{bookmark::Bookmark, shared, Timestamp};
 
 use bookmark_command::{Command, CommandErr};
 
@@ -19,10 +19,11 @@ impl Command for New {
 
         let index = bookmarks.storage.len();
 
-        bookmarks.storage.push(Bookmark::new(
+        bookmarks.storage.push(Bookmark::new_at(
             "no url",
             "no info",
             std::iter::empty::<&str>(),
+            Timestamp::now(),
         ));
 
         bookmarks.buffer.reset();
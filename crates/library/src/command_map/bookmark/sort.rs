@@ -1,25 +1,306 @@
-use crate::{bookmark::Bookmark, shared};
+use crate::{bookmark::Bookmark, container::Change, shared, url};
 use bookmark_command::{Command, CommandErr};
+use bookmark_storage::{Property, Storeable};
+use std::cmp::Ordering;
 
-pub fn build(bookmarks: shared::BufferStorage<Bookmark>) -> Box<dyn Command> {
-    Box::new(move |args: &[_]| {
-        if !args.is_empty() {
-            return Err(CommandErr::Usage(
-                "sort should be called without any arguments".into(),
-            ));
+/// Flag making only the current buffer be sorted, leaving storage as is.
+const VIEW: &str = "--view";
+
+/// Flag making text be compared without regard to case.
+const IGNORE_CASE: &str = "--ignore-case";
+
+/// Flag making runs of digits in text be compared by their numeric value.
+const NATURAL: &str = "--natural";
+
+/// What bookmarks may be sorted by.
+#[derive(Debug, Clone)]
+enum Key {
+    Domain,
+    TagCount,
+    Property(String),
+}
+
+/// Value of a [Key] for a single bookmark.
+#[derive(Debug, Clone)]
+enum Value {
+    Text(String),
+    Count(usize),
+    List(Vec<String>),
+}
+
+/// A [Key] and the direction to sort it in.
+#[derive(Debug, Clone)]
+struct SortKey {
+    key: Key,
+    descending: bool,
+}
+
+impl SortKey {
+    fn parse(arg: &str) -> Result<Self, CommandErr> {
+        let (key, descending) = match arg.split_once(':') {
+            None => (arg, false),
+            Some((key, "asc")) => (key, false),
+            Some((key, "desc")) => (key, true),
+            Some((_, direction)) => {
+                return Err(CommandErr::Usage(format!(
+                    "{direction} is not a sort direction, expected asc or desc"
+                )))
+            }
+        };
+
+        let key = match key {
+            "domain" => Key::Domain,
+            "tag-count" => Key::TagCount,
+            property => Key::Property(property.into()),
+        };
+
+        Ok(Self { key, descending })
+    }
+
+    fn value(&self, bookmark: &Bookmark, ignore_case: bool) -> Result<Value, CommandErr> {
+        let text = |text: &str| {
+            if ignore_case {
+                text.to_lowercase()
+            } else {
+                text.into()
+            }
+        };
+
+        Ok(match self.key {
+            Key::Domain => Value::Text(text(url::domain(bookmark.url()))),
+            Key::TagCount => Value::Count(bookmark.tags().count()),
+            Key::Property(ref property) => match bookmark.get(property)? {
+                Property::Single(value) => Value::Text(text(&value)),
+                Property::List(values) => {
+                    Value::List(values.iter().map(|value| text(value)).collect())
+                }
+            },
+        })
+    }
+}
+
+/// Compare text, optionally comparing runs of digits by their numeric value.
+fn compare_text(a: &str, b: &str, natural: bool) -> Ordering {
+    if !natural {
+        return a.cmp(b);
+    }
+
+    let (mut a, mut b) = (a, b);
+    loop {
+        let digits = |s: &str| s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        let (a_digits, b_digits) = (digits(a), digits(b));
+
+        let ordering = if a_digits > 0 && b_digits > 0 {
+            let (a_num, b_num) = (
+                a[..a_digits].trim_start_matches('0'),
+                b[..b_digits].trim_start_matches('0'),
+            );
+            let ordering = a_num.len().cmp(&b_num.len()).then(a_num.cmp(b_num));
+            (a, b) = (&a[a_digits..], &b[b_digits..]);
+            ordering
+        } else {
+            let mut a_chars = a.chars();
+            let mut b_chars = b.chars();
+            let ordering = a_chars.next().cmp(&b_chars.next());
+            (a, b) = (a_chars.as_str(), b_chars.as_str());
+            ordering
+        };
+
+        if ordering.is_ne() || (a.is_empty() && b.is_empty()) {
+            return ordering;
         }
+    }
+}
+
+fn compare_values(a: &Value, b: &Value, natural: bool) -> Ordering {
+    match (a, b) {
+        (Value::Text(a), Value::Text(b)) => compare_text(a, b, natural),
+        (Value::Count(a), Value::Count(b)) => a.cmp(b),
+        (Value::List(a), Value::List(b)) => a
+            .iter()
+            .zip(b)
+            .map(|(a, b)| compare_text(a, b, natural))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or_else(|| a.len().cmp(&b.len())),
+        // values of the same key are always of the same kind
+        _ => Ordering::Equal,
+    }
+}
+
+fn compare(keys: &[SortKey], a: &[Value], b: &[Value], natural: bool) -> Ordering {
+    keys.iter()
+        .zip(a.iter().zip(b))
+        .map(|(key, (a, b))| {
+            let ordering = compare_values(a, b, natural);
+            if key.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        })
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+pub fn build(bookmarks: shared::BufferStorage<Bookmark>) -> Box<dyn Command> {
+    Box::new(move |args: &[String]| {
+        let (view, args) = bookmark_command::take_flag(args, VIEW);
+        let (ignore_case, args) = bookmark_command::take_flag(&args, IGNORE_CASE);
+        let (natural, args) = bookmark_command::take_flag(&args, NATURAL);
+
+        let keys = if args.is_empty() {
+            vec![SortKey::parse("url")?]
+        } else {
+            args.iter()
+                .map(|arg| SortKey::parse(arg))
+                .collect::<Result<Vec<_>, _>>()?
+        };
 
         let mut bookmarks = bookmarks.write();
 
-        bookmarks.storage.sort_by(|a, b| {
-            a.url()
-                .partial_cmp(b.url())
-                .expect("failed to perform a partial_cmp between two bookmark urls")
-        });
+        let values = bookmarks
+            .storage
+            .iter()
+            .map(|bookmark| {
+                keys.iter()
+                    .map(|key| key.value(bookmark, ignore_case))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
 
-        bookmarks.buffer.reset();
-        bookmarks.selected.clear();
+        if view {
+            let len = bookmarks.storage.len();
+            bookmarks.push_history();
+            bookmarks.buffer.sort_by(len, |a, b| {
+                compare(&keys, &values[*a], &values[*b], natural)
+            });
+        } else {
+            let mut order = (0..values.len()).collect::<Vec<_>>();
+            order.sort_by(|a, b| compare(&keys, &values[*a], &values[*b], natural));
+            if order.iter().enumerate().all(|(i, index)| i == *index) {
+                return Ok(());
+            }
+
+            let inverse = bookmarks.permute(&order);
+            bookmarks.record("sort", Change::Permute(inverse));
+        }
 
         Ok(())
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn natural_order() {
+        let mut items = ["a10", "a2", "a02b", "b1", "a1", "a"];
+        items.sort_by(|a, b| compare_text(a, b, true));
+        assert_eq!(items, ["a", "a1", "a2", "a02b", "a10", "b1"]);
+
+        assert_eq!(compare_text("a10", "a2", false), Ordering::Less);
+    }
+
+    #[test]
+    pub fn sort_keys() {
+        let bookmarks = shared::BufferStorage::<Bookmark>::default();
+        bookmarks.write().storage.extend([
+            Bookmark::new("https://b.org/2", "B", ["x"].into_iter()),
+            Bookmark::new("https://a.org/10", "a", ["x", "y"].into_iter()),
+            Bookmark::new("https://www.b.org/10", "c", std::iter::empty::<&str>()),
+        ]);
+        let mut sort = build(bookmarks.clone());
+        let urls = || {
+            bookmarks
+                .read()
+                .iter()
+                .map(|bookmark| bookmark.url().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        sort.call(&["description".into()])
+            .expect("sort should succeed");
+        assert_eq!(
+            urls(),
+            [
+                "https://b.org/2",
+                "https://a.org/10",
+                "https://www.b.org/10"
+            ]
+        );
+
+        sort.call(&["domain:desc".into(), "url".into(), NATURAL.into()])
+            .expect("sort should succeed");
+        assert_eq!(
+            urls(),
+            [
+                "https://b.org/2",
+                "https://www.b.org/10",
+                "https://a.org/10"
+            ]
+        );
+
+        bookmarks
+            .write()
+            .filter_in_place(|bookmark| bookmark.url().contains("10"));
+        sort.call(&["tag-count:desc".into(), VIEW.into()])
+            .expect("sort should succeed");
+        assert_eq!(urls(), ["https://a.org/10", "https://www.b.org/10"]);
+        assert_eq!(bookmarks.read().storage[0].url(), "https://b.org/2");
+
+        assert!(sort.call(&["size".into()]).is_err());
+        assert!(sort.call(&["url:up".into()]).is_err());
+    }
+
+    #[test]
+    pub fn keep_buffers() {
+        let bookmarks = shared::BufferStorage::<Bookmark>::default();
+        bookmarks.write().storage.extend(
+            ["c", "a", "d", "b"].map(|url| Bookmark::new(url, "", std::iter::empty::<&str>())),
+        );
+        {
+            let mut bookmarks = bookmarks.write();
+            bookmarks.filter_indices_in_place(|i| *i != 2);
+            bookmarks.name_buffer("named");
+            bookmarks.selected.replace(0);
+        }
+        let state = || {
+            let bookmarks = bookmarks.read();
+            let urls = |indices: &mut dyn Iterator<Item = usize>| {
+                indices
+                    .map(|i| bookmarks.storage[i].url().to_string())
+                    .collect::<Vec<_>>()
+            };
+            (
+                urls(&mut bookmarks.buffer.iter()),
+                bookmarks.named_buffer("named").map(|b| urls(&mut b.iter())),
+                bookmarks
+                    .selected
+                    .index()
+                    .map(|i| bookmarks.storage[i].url().to_string()),
+            )
+        };
+        let before = state();
+
+        build(bookmarks.clone())
+            .call(&[])
+            .expect("sort should succeed");
+        assert_eq!(
+            bookmarks
+                .read()
+                .storage
+                .iter()
+                .map(|bookmark| bookmark.url().to_string())
+                .collect::<Vec<_>>(),
+            ["a", "b", "c", "d"]
+        );
+        assert_eq!(bookmarks.read().selected.index(), Some(2));
+        assert_eq!(state(), before);
+
+        assert_eq!(bookmarks.write().undo().as_deref(), Some("sort"));
+        assert_eq!(bookmarks.read().storage[0].url(), "c");
+        assert_eq!(bookmarks.read().selected.index(), Some(0));
+        assert_eq!(state(), before);
+    }
+}
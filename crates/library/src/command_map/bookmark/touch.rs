@@ -0,0 +1,29 @@
+use crate::{bookmark::Bookmark, shared, Timestamp};
+use bookmark_command::Command;
+
+/// Wrap a command editing the selected bookmark such that the bookmark is marked as modified when
+/// the command succeeds, unless a timestamp was what was edited.
+pub fn build(
+    mut command: Box<dyn Command>,
+    bookmarks: shared::BufferStorage<Bookmark>,
+) -> Box<dyn Command> {
+    Box::new(move |args: &[String]| {
+        command.call(args)?;
+
+        let mut bookmarks = bookmarks.write();
+        if let (Some(property), Ok((_, bookmark))) =
+            (args.first(), bookmarks.get_index_and_selected_mut())
+        {
+            mark_modified(bookmark, property);
+        }
+
+        Ok(())
+    })
+}
+
+/// Mark a bookmark as modified after a property was edited, unless the property is a timestamp.
+pub fn mark_modified(bookmark: &mut Bookmark, property: &str) {
+    if !Bookmark::TIMESTAMPS.contains(&property) {
+        bookmark.set_modified(Some(Timestamp::now()));
+    }
+}
@@ -1,32 +1,227 @@
-use crate::{bookmark::Bookmark, shared};
+use crate::{bookmark::Bookmark, container::Change, shared, url, Timestamp};
 use bookmark_command::{Command, CommandErr};
+use std::collections::HashMap;
+
+/// Flag making unique only list groups of duplicates without merging them.
+const DRY_RUN: &str = "--dry-run";
+
+/// Group indices of bookmarks by their normalized url, groups and their contents are in the order
+/// they first occur in.
+fn duplicate_groups(bookmarks: &[Bookmark]) -> Vec<(String, Vec<usize>)> {
+    let mut groups = Vec::<(String, Vec<usize>)>::new();
+    let mut group_map = HashMap::<String, usize>::new();
+
+    for (i, bookmark) in bookmarks.iter().enumerate() {
+        let normalized = url::normalize(bookmark.url());
+        if let Some(&group) = group_map.get(&normalized) {
+            groups[group].1.push(i);
+        } else {
+            group_map.insert(normalized.clone(), groups.len());
+            groups.push((normalized, vec![i]));
+        }
+    }
+
+    groups.retain(|(_, indices)| indices.len() > 1);
+    groups
+}
+
+/// Merge a duplicate into a bookmark, tags are unioned, the longest description is kept, the
+/// earliest added and latest visited times are kept. Returns whether or not the bookmark changed.
+fn merge(into: &mut Bookmark, duplicate: &Bookmark) -> bool {
+    let mut changed = false;
+
+    if duplicate.description().chars().count() > into.description().chars().count() {
+        into.set_description(duplicate.description());
+        changed = true;
+    }
+
+    let missing = duplicate
+        .tags()
+        .filter(|tag| !into.tags().any(|existing| existing == *tag))
+        .map(String::from)
+        .collect::<Vec<_>>();
+    for tag in &missing {
+        into.push_tag(tag);
+    }
+    changed |= !missing.is_empty();
+
+    if let Some(added) = duplicate.added() {
+        if into.added().is_none_or(|existing| added < existing) {
+            into.set_added(Some(added));
+            changed = true;
+        }
+    }
+    if let Some(visited) = duplicate.visited() {
+        if into.visited().is_none_or(|existing| visited > existing) {
+            into.set_visited(Some(visited));
+            changed = true;
+        }
+    }
+
+    changed
+}
 
 pub fn build(bookmarks: shared::BufferStorage<Bookmark>) -> Box<dyn Command> {
-    Box::new(move |args: &[_]| {
+    Box::new(move |args: &[String]| {
+        let (dry_run, args) = bookmark_command::take_flag(args, DRY_RUN);
         if !args.is_empty() {
-            return Err(CommandErr::Usage(
-                "unique should be called without any arguments".into(),
-            ));
+            return Err(CommandErr::Usage(format!(
+                "unique should be called without any arguments other than {DRY_RUN}"
+            )));
         }
 
-        let mut removed_count = 0usize;
         let mut bookmarks = bookmarks.write();
+        let groups = duplicate_groups(&bookmarks.storage);
 
-        bookmarks.storage.sort_by(|a, b| a.url().cmp(b.url()));
-        bookmarks.storage.dedup_by(|a, b| {
-            if a.url().eq_ignore_ascii_case(b.url()) {
-                removed_count += 1;
-                true
-            } else {
-                false
+        if dry_run {
+            for (normalized, indices) in &groups {
+                println!("{normalized}:");
+                for &i in indices {
+                    println!("\t{i}. {}", bookmarks.storage[i].url());
+                }
             }
-        });
+            println!("found {} groups of duplicates", groups.len());
+            return Ok(());
+        }
 
-        println!("remvoved {removed_count} bookmarks");
+        let now = Timestamp::now();
+        let mut removed = Vec::new();
+        let mut replaced = Vec::new();
+        let storage = bookmarks.storage.as_mut();
+        for (_, indices) in &groups {
+            let [first, duplicates @ ..] = &indices[..] else {
+                continue;
+            };
 
-        bookmarks.selected.clear();
-        bookmarks.buffer.reset();
+            let original = storage[*first].clone();
+            let mut changed = false;
+            for &duplicate in duplicates {
+                let (head, tail) = storage.split_at_mut(duplicate);
+                changed |= merge(&mut head[*first], &tail[0]);
+                removed.push(duplicate);
+            }
+
+            if changed {
+                storage[*first].set_modified(Some(now));
+                replaced.push((*first, original));
+            }
+        }
+
+        let items = bookmarks.remove_indexed(removed);
+        if !items.is_empty() {
+            bookmarks.record(
+                "unique",
+                Change::Many(vec![Change::Insert(items), Change::Replace(replaced)]),
+            );
+        }
+
+        println!(
+            "merged {} groups, removing {} bookmarks",
+            groups.len(),
+            groups
+                .iter()
+                .map(|(_, indices)| indices.len() - 1)
+                .sum::<usize>()
+        );
 
         Ok(())
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bookmark_storage::Storeable;
+
+    #[test]
+    pub fn merge_duplicates() {
+        let bookmarks = shared::BufferStorage::<Bookmark>::default();
+        let at = Timestamp::from_secs;
+        bookmarks.write().storage.extend([
+            Bookmark::new("https://example.com/a", "short", ["x"].into_iter()),
+            Bookmark::new("https://other.org", "other", std::iter::empty::<&str>()),
+            Bookmark::new(
+                "http://www.example.com/a/?utm_source=feed#top",
+                "a longer description",
+                ["y", "x"].into_iter(),
+            ),
+            Bookmark::new("example.com/a", "", ["z"].into_iter()),
+        ]);
+        {
+            let mut bookmarks = bookmarks.write();
+            let storage = bookmarks.storage.as_mut();
+            storage[0].set_added(at(20)).set_visited(at(30));
+            storage[2].set_added(at(10)).set_visited(at(40));
+        }
+        let first_id = bookmarks.read().storage[0].id();
+
+        assert_eq!(
+            duplicate_groups(&bookmarks.read().storage),
+            [("example.com/a".to_string(), vec![0, 2, 3])]
+        );
+
+        let mut unique = build(bookmarks.clone());
+        unique
+            .call(&[DRY_RUN.into()])
+            .expect("dry run should succeed");
+        assert_eq!(bookmarks.read().storage.len(), 4);
+
+        unique.call(&[]).expect("unique should succeed");
+        let bookmarks = bookmarks.read();
+        assert_eq!(bookmarks.storage.len(), 2);
+
+        let merged = &bookmarks.storage[0];
+        assert_eq!(merged.id(), first_id);
+        assert_eq!(merged.url(), "https://example.com/a");
+        assert_eq!(merged.description(), "a longer description");
+        assert_eq!(merged.tags().collect::<Vec<_>>(), ["x", "y", "z"]);
+        assert_eq!(merged.added(), at(10));
+        assert_eq!(merged.visited(), at(40));
+        assert!(merged.modified().is_some());
+        assert!(merged.is_edited());
+        assert_eq!(bookmarks.storage[1].url(), "https://other.org");
+    }
+
+    #[test]
+    pub fn undo_merged_timestamps() {
+        let bookmarks = shared::BufferStorage::<Bookmark>::default();
+        let at = Timestamp::from_secs;
+        bookmarks.write().storage.extend([
+            Bookmark::new("https://example.com", "a", ["x"].into_iter()),
+            Bookmark::new("https://other.org", "b", std::iter::empty::<&str>()),
+            Bookmark::new("example.com", "", ["x"].into_iter()),
+        ]);
+        {
+            let mut bookmarks = bookmarks.write();
+            bookmarks.storage.as_mut()[2]
+                .set_added(at(10))
+                .set_visited(at(20));
+            bookmarks.filter_indices_in_place(|i| *i > 0);
+            bookmarks.name_buffer("rest");
+            bookmarks.selected.replace(1);
+        }
+
+        build(bookmarks.clone())
+            .call(&[])
+            .expect("unique should succeed");
+        {
+            let bookmarks = bookmarks.read();
+            assert_eq!(bookmarks.storage[0].visited(), at(20));
+            assert_eq!(bookmarks.buffer.iter().collect::<Vec<_>>(), [1]);
+            assert_eq!(bookmarks.selected.index(), Some(1));
+            assert_eq!(
+                bookmarks
+                    .named_buffer("rest")
+                    .map(|buffer| buffer.iter().collect::<Vec<_>>()),
+                Some(vec![1])
+            );
+        }
+
+        assert_eq!(bookmarks.write().undo().as_deref(), Some("unique"));
+        let bookmarks = bookmarks.read();
+        assert_eq!(bookmarks.storage.len(), 3);
+        assert_eq!(bookmarks.storage[0].added(), None);
+        assert_eq!(bookmarks.storage[0].visited(), None);
+        assert_eq!(bookmarks.storage[2].visited(), at(20));
+    }
+}
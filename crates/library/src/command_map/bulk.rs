@@ -0,0 +1,327 @@
+use crate::{container, shared};
+use bookmark_command::{Command, CommandErr};
+use bookmark_storage::{Property, Storeable};
+
+/// Flag used to apply an edit without a preview.
+const YES: &str = "--yes";
+
+/// Amount of changes shown in a preview.
+const PREVIEW_LIMIT: usize = 5;
+
+#[derive(Debug)]
+enum Edit {
+    Set(Vec<String>),
+    Push(Vec<String>),
+    Remove(Vec<String>),
+    Replace(regex::Regex, String),
+}
+
+impl Edit {
+    fn parse(operation: &str, values: &[String]) -> Result<Self, CommandErr> {
+        Ok(match operation {
+            "set" => Self::Set(values.to_vec()),
+            "push" => Self::Push(values.to_vec()),
+            "remove" => Self::Remove(values.to_vec()),
+            "replace" => {
+                let [pattern, replacement] = values else {
+                    return Err(CommandErr::Usage(
+                        "bulk replace takes a pattern and a replacement".into(),
+                    ));
+                };
+                let re = regex::Regex::new(pattern)
+                    .map_err(|_| CommandErr::Execution(format!("invalid pattern /{pattern}/")))?;
+                Self::Replace(re, replacement.clone())
+            }
+            operation => {
+                return Err(CommandErr::Usage(format!(
+                    "unknown bulk operation {operation}, expected set, push, remove or replace"
+                )))
+            }
+        })
+    }
+
+    /// Get the edited value of a property, none if the edit would not change it.
+    fn edited(&self, property: &str, current: Property) -> Result<Option<Property>, CommandErr> {
+        let edited = match (self, current) {
+            (Self::Set(values), Property::Single(current)) => {
+                let [value] = values.as_slice() else {
+                    return Err(CommandErr::Execution(format!(
+                        "property {property} takes only a single value"
+                    )));
+                };
+                (*value != current).then(|| Property::Single(value.clone()))
+            }
+            (Self::Set(values), Property::List(current)) => {
+                (*values != current).then(|| Property::List(values.clone()))
+            }
+            (Self::Push(values), Property::List(mut current)) => {
+                let len = current.len();
+                for value in values {
+                    if !current.contains(value) {
+                        current.push(value.clone());
+                    }
+                }
+                (current.len() != len).then_some(Property::List(current))
+            }
+            (Self::Remove(values), Property::List(mut current)) => {
+                let len = current.len();
+                current.retain(|value| !values.contains(value));
+                (current.len() != len).then_some(Property::List(current))
+            }
+            (Self::Push(_) | Self::Remove(_), Property::Single(_)) => {
+                return Err(CommandErr::Execution(format!(
+                    "push and remove can only be used on list properties, {property} is not one"
+                )));
+            }
+            (Self::Replace(re, replacement), Property::Single(current)) => {
+                let replaced = re.replace_all(&current, replacement.as_str());
+                (replaced != current).then(|| Property::Single(replaced.into_owned()))
+            }
+            (Self::Replace(re, replacement), Property::List(current)) => {
+                let replaced = current
+                    .iter()
+                    .map(|value| re.replace_all(value, replacement.as_str()).into_owned())
+                    .collect::<Vec<_>>();
+                (replaced != current).then_some(Property::List(replaced))
+            }
+        };
+
+        Ok(edited)
+    }
+}
+
+fn describe(property: &Property) -> String {
+    match property {
+        Property::Single(value) => format!("\"{value}\""),
+        Property::List(values) => format!("{values:?}"),
+    }
+}
+
+/// Change to a single item, along with the value it had when the change was computed.
+#[derive(PartialEq)]
+struct Change {
+    index: usize,
+    current: Property,
+    edited: Property,
+}
+
+/// Amount of items in the buffer.
+fn buffer_count<T>(buffer_storage: &container::BufferStorage<T>) -> usize {
+    buffer_storage
+        .buffer
+        .count()
+        .unwrap_or(buffer_storage.storage.len())
+}
+
+/// Compute the changes an edit makes to the items in the buffer.
+fn changes<T: Storeable>(
+    buffer_storage: &container::BufferStorage<T>,
+    edit: &Edit,
+    property: &str,
+) -> Result<Vec<Change>, CommandErr> {
+    let mut changes = Vec::new();
+    for index in buffer_storage
+        .buffer
+        .iter()
+        .take(buffer_count(buffer_storage))
+    {
+        let current = buffer_storage.storage[index].get(property)?;
+        if let Some(edited) = edit.edited(property, current.clone())? {
+            changes.push(Change {
+                index,
+                current,
+                edited,
+            });
+        }
+    }
+    Ok(changes)
+}
+
+/// Edit awaiting confirmation, it is recomputed when confirmed and discarded if it would no longer
+/// make the changes previewed.
+struct Pending {
+    edit: Edit,
+    property: String,
+    changes: Vec<Change>,
+}
+
+/// Build a command editing every item in the buffer, `touch` is called on every item changed along
+/// with the name of the changed property. The items replaced are recorded such that the edit may be
+/// undone.
+pub fn build<T>(
+    buffer_storage: shared::BufferStorage<T>,
+    mut touch: impl FnMut(&mut T, &str) + 'static,
+) -> Box<dyn Command>
+where
+    T: Storeable + Clone + 'static,
+{
+    let mut pending: Option<Pending> = None;
+
+    let mut apply = move |buffer_storage: &shared::BufferStorage<T>,
+                          pending: Pending|
+          -> Result<(), CommandErr> {
+        let mut buffer_storage = buffer_storage.write();
+        let mut replaced = Vec::with_capacity(pending.changes.len());
+        let mut result = Ok(());
+        for Change { index, edited, .. } in pending.changes {
+            let item = &mut buffer_storage.storage[index];
+            let previous = item.clone();
+            if let Err(err) = item.set(&pending.property, edited) {
+                result = Err(err.into());
+                break;
+            }
+            touch(item, &pending.property);
+            replaced.push((index, previous));
+        }
+
+        println!("changed {} items", replaced.len());
+        // recorded even if an edit failed, such that the edits made before it may be undone
+        if !replaced.is_empty() {
+            buffer_storage.record("bulk", container::Change::Replace(replaced));
+        }
+        result
+    };
+
+    Box::new(move |args: &[String]| {
+        match args.first().map(String::as_str) {
+            Some("confirm") => {
+                let pending = pending
+                    .take()
+                    .ok_or_else(|| CommandErr::Execution("no bulk edit to confirm".into()))?;
+                if changes(&buffer_storage.read(), &pending.edit, &pending.property)?
+                    != pending.changes
+                {
+                    return Err(CommandErr::Execution(
+                        "items were changed since the preview, the edit was discarded".into(),
+                    ));
+                }
+                return apply(&buffer_storage, pending);
+            }
+            Some("cancel") => {
+                pending = None;
+                return Ok(());
+            }
+            _ => (),
+        }
+
+        let (yes, args) = bookmark_command::take_flag(args, YES);
+        let [operation, property, values @ ..] = args.as_slice() else {
+            return Err(CommandErr::Usage(
+                "bulk should be called with an operation and a property".into(),
+            ));
+        };
+        let edit = Edit::parse(operation, values)?;
+        let (changes, count) = {
+            let buffer_storage = buffer_storage.read();
+            (
+                changes(&buffer_storage, &edit, property)?,
+                buffer_count(&buffer_storage),
+            )
+        };
+
+        if !yes {
+            println!("{} of {count} items would change", changes.len());
+            for change in changes.iter().take(PREVIEW_LIMIT) {
+                println!(
+                    "{}. {} -> {}",
+                    change.index,
+                    describe(&change.current),
+                    describe(&change.edited)
+                );
+            }
+            if changes.len() > PREVIEW_LIMIT {
+                println!("...");
+            }
+        }
+
+        let edit = Pending {
+            edit,
+            property: property.clone(),
+            changes,
+        };
+
+        if yes {
+            pending = None;
+            apply(&buffer_storage, edit)
+        } else {
+            pending = Some(edit);
+            println!("use \"bulk confirm\" to apply or \"bulk cancel\" to discard");
+            Ok(())
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Bookmark;
+
+    #[test]
+    pub fn bulk_edit() {
+        let bookmarks = shared::BufferStorage::<Bookmark>::default();
+        bookmarks.write().storage.extend([
+            Bookmark::new("http://a.org", "A", ["rust"].into_iter()),
+            Bookmark::new("http://b.org", "B", ["go"].into_iter()),
+            Bookmark::new("http://c.org", "C", ["rust", "old"].into_iter()),
+        ]);
+        bookmarks
+            .write()
+            .filter_in_place(|bookmark| bookmark.url() != "http://b.org");
+
+        let touched = std::rc::Rc::new(std::cell::Cell::new(0));
+        let mut bulk = build(bookmarks.clone(), {
+            let touched = touched.clone();
+            move |_, _| touched.set(touched.get() + 1)
+        });
+        let tags = |index: usize| {
+            bookmarks.read().storage[index]
+                .tags()
+                .map(String::from)
+                .collect::<Vec<_>>()
+        };
+        let args = |args: &[&str]| {
+            args.iter()
+                .map(|arg| String::from(*arg))
+                .collect::<Vec<_>>()
+        };
+
+        bulk.call(&args(&["push", "tags", "lang"]))
+            .expect("preview should succeed");
+        assert_eq!(tags(0), ["rust"]);
+        bulk.call(&args(&["confirm"])).expect("edit should apply");
+        assert_eq!(tags(0), ["rust", "lang"]);
+        assert_eq!(tags(1), ["go"]);
+        assert_eq!(touched.get(), 2);
+        assert!(bulk.call(&args(&["confirm"])).is_err());
+        assert_eq!(bookmarks.write().undo().as_deref(), Some("bulk"));
+        assert_eq!(tags(0), ["rust"]);
+        assert_eq!(bookmarks.write().redo().as_deref(), Some("bulk"));
+        assert_eq!(tags(0), ["rust", "lang"]);
+
+        bulk.call(&args(&[YES, "remove", "tags", "old"]))
+            .expect("edit should apply");
+        assert_eq!(tags(2), ["rust", "lang"]);
+        assert_eq!(touched.get(), 3);
+
+        bulk.call(&args(&[YES, "replace", "url", "^http:", "https:"]))
+            .expect("edit should apply");
+        assert_eq!(bookmarks.read().storage[2].url(), "https://c.org");
+        assert_eq!(bookmarks.read().storage[1].url(), "http://b.org");
+
+        bulk.call(&args(&["set", "description", "D"]))
+            .expect("preview should succeed");
+        bookmarks.write().storage[2].set_description("E");
+        assert!(bulk.call(&args(&["confirm"])).is_err());
+        assert_eq!(bookmarks.read().storage[0].description(), "A");
+
+        bulk.call(&args(&["push", "tags", "new"]))
+            .expect("preview should succeed");
+        bookmarks.write().storage.swap(0, 1);
+        assert!(bulk.call(&args(&["confirm"])).is_err());
+        assert_eq!(tags(0), ["go"]);
+
+        assert!(bulk.call(&args(&["push", "url", "x"])).is_err());
+        assert!(bulk.call(&args(&["set", "size", "x"])).is_err());
+        assert!(bulk.call(&args(&["replace", "url", "(", "x"])).is_err());
+    }
+}
@@ -1,11 +1,18 @@
 pub mod apply;
+pub mod check;
+
+use bookmark_command::Command;
+use std::collections::HashSet;
 
 use crate::{
     bookmark::Bookmark,
     category::Category,
     command_map::{
-        count, list, load, print, push, save, select, set, Builder as CommandMapBuilder, CommandMap,
+        bulk, combine, compact, count, delete, history, journal, journaled, list, load, print,
+        push, save, select, set, Builder as CommandMapBuilder, Scope,
     },
+    container::{self, Change, SharedChange},
+    info::Info,
     shared,
 };
 
@@ -13,11 +20,29 @@ pub fn build(
     name: String,
     categories: shared::BufferStorage<Category>,
     bookmarks: shared::BufferStorage<Bookmark>,
-) -> Box<CommandMap<'static>> {
+    infos: shared::BufferStorage<Info>,
+) -> Box<dyn Command> {
+    let builder = history::push_commands(CommandMapBuilder::new().name(name), &categories);
+    let builder = combine::push_operations(builder, &categories);
+    let builder = journal::push_commands(builder, &categories);
+
     Box::new(
-        CommandMapBuilder::new()
-            .name(name)
-            .push("load", None, load::Load::build(categories.clone()))
+        builder
+            .push(
+                "load",
+                None,
+                journaled(
+                    "load",
+                    Scope::Appended,
+                    load::Load::build(categories.clone()),
+                    categories.clone(),
+                ),
+            )
+            .push(
+                "compact",
+                Some("remove content no longer used by categories"),
+                compact::Compact::build(categories.clone()),
+            )
             .push(
                 "list",
                 Some("list categories"),
@@ -28,7 +53,16 @@ pub fn build(
                 Some("count amount of categories"),
                 count::Count::build(categories.clone()),
             )
-            .push("set", None, set::Set::build(categories.clone()))
+            .push(
+                "set",
+                None,
+                journaled(
+                    "set",
+                    Scope::Selected,
+                    set::Set::build(categories.clone()),
+                    categories.clone(),
+                ),
+            )
             .push("save", None, save::Save::build(categories.clone()))
             .push(
                 "print",
@@ -38,14 +72,158 @@ pub fn build(
             .push(
                 "push",
                 Some("push a value onto a list field"),
-                push::build(categories.clone()),
+                journaled(
+                    "push",
+                    Scope::Selected,
+                    push::build(categories.clone()),
+                    categories.clone(),
+                ),
+            )
+            .push(
+                "bulk",
+                Some("edit every item in the buffer, showing a preview to confirm unless --yes is given, replace uses a regex on every value of the property\nusage: bulk [--yes] set|push|remove PROPERTY [VALUE ...]\nusage: bulk [--yes] replace PROPERTY PATTERN REPLACEMENT\nusage: bulk confirm|cancel"),
+                bulk::build(categories.clone(), |_, _| ()),
+            )
+            .push(
+                "delete",
+                Some("delete the selected category or a category by index or id, removing it as a subcategory\nusage: delete [INDEX|ID]"),
+                delete::build(categories.clone(), {
+                    let infos = infos.clone();
+                    move |categories, removed| remove_references(categories, &infos, removed)
+                }),
+            )
+            .push(
+                "delete-buffer",
+                Some("delete every category in the buffer, only showing the amount unless --yes is given\nusage: delete-buffer [--yes]"),
+                delete::build_buffer(categories.clone(), {
+                    let infos = infos.clone();
+                    move |categories, removed| remove_references(categories, &infos, removed)
+                }),
             )
             .push("select", None, select::Select::build(categories.clone()))
             .push(
                 "apply",
-                Some("filter bookmarks in buffer by selected category"),
-                apply::build(bookmarks /*.clone()*/, categories /*.clone()*/),
+                Some("filter bookmarks in buffer by selected category, with --recursive bookmarks matching any subcategory are kept as well\nusage: apply [--recursive]"),
+                apply::build(bookmarks /*.clone()*/, categories.clone()),
+            )
+            .push(
+                "check",
+                Some("report cycles, missing subcategories, categories not reachable from any top level category, duplicate ids and malformed identifiers\nusage: check"),
+                check::build(categories /*.clone()*/, infos /*.clone()*/),
             )
             .build(),
     )
 }
+
+/// Remove removed categories from the subcategories of the remaining categories and from the top
+/// level categories of infos, returns the change undoing the edits to both.
+fn remove_references(
+    categories: &mut container::BufferStorage<Category>,
+    infos: &shared::BufferStorage<Info>,
+    removed: &[(usize, Category)],
+) -> Option<Change<Category>> {
+    let ids = removed
+        .iter()
+        .map(|(_, category)| category.id())
+        .collect::<HashSet<_>>();
+
+    let mut replaced = Vec::new();
+    for (index, category) in categories.storage.iter_mut().enumerate() {
+        if category.subcategories().any(|id| ids.contains(id)) {
+            replaced.push((index, category.clone()));
+            let kept = category
+                .subcategories()
+                .filter(|id| !ids.contains(id))
+                .map(String::from)
+                .collect::<Vec<_>>();
+            category.set_subcategories(kept.iter());
+        }
+    }
+
+    let mut replaced_infos = Vec::new();
+    for (index, info) in infos.write().storage.iter_mut().enumerate() {
+        if info.categories().any(|id| ids.contains(id)) {
+            replaced_infos.push((index, info.clone()));
+            let kept = info
+                .categories()
+                .filter(|id| !ids.contains(id))
+                .map(String::from)
+                .collect::<Vec<_>>();
+            info.set_categories(kept.iter());
+        }
+    }
+
+    let mut changes = Vec::new();
+    if !replaced.is_empty() {
+        changes.push(Change::Replace(replaced));
+    }
+    if !replaced_infos.is_empty() {
+        changes.push(Change::Linked(Box::new(SharedChange {
+            buffer_storage: infos.clone(),
+            change: Change::Replace(replaced_infos),
+        })));
+    }
+    (!changes.is_empty()).then_some(Change::Many(changes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn delete_removes_references() {
+        let category = |id: &str, subcategories: &[&str]| {
+            Category::new(id, id, "", std::iter::empty::<&str>(), subcategories.iter())
+        };
+
+        let categories = shared::BufferStorage::<Category>::default();
+        categories.write().storage.extend([
+            category("a", &["b", "c"]),
+            category("b", &["c"]),
+            category("c", &[]),
+        ]);
+        let infos = shared::BufferStorage::<Info>::default();
+        infos.write().storage.push(Info::new(
+            ["a", "c"].into_iter(),
+            std::iter::empty::<&str>(),
+        ));
+
+        let mut map = build(
+            "category".into(),
+            categories.clone(),
+            shared::BufferStorage::default(),
+            infos.clone(),
+        );
+        let subcategories = || {
+            categories
+                .read()
+                .storage
+                .iter()
+                .map(|category| {
+                    category
+                        .subcategories()
+                        .map(String::from)
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        };
+        let info_categories = || {
+            infos.read().storage[0]
+                .categories()
+                .map(String::from)
+                .collect::<Vec<_>>()
+        };
+
+        map.call(&["delete".into(), "c".into()])
+            .expect("category should be deleted");
+        assert_eq!(subcategories(), [vec!["b"], vec![]]);
+        assert_eq!(info_categories(), ["a"]);
+
+        map.call(&["undo".into()]).expect("delete should be undone");
+        assert_eq!(subcategories(), [vec!["b", "c"], vec!["c"], vec![]]);
+        assert_eq!(info_categories(), ["a", "c"]);
+
+        map.call(&["redo".into()]).expect("delete should be redone");
+        assert_eq!(info_categories(), ["a"]);
+    }
+}
@@ -1,15 +1,19 @@
 use crate::{bookmark::Bookmark, category::Category, shared};
 use bookmark_command::CommandErr;
 
+/// Flag used to also apply subcategories.
+const RECURSIVE: &str = "--recursive";
+
 pub fn build(
     bookmarks: shared::BufferStorage<Bookmark>,
     categories: shared::BufferStorage<Category>,
 ) -> Box<dyn bookmark_command::Command> {
     Box::new(move |args: &[_]| {
+        let (recursive, args) = bookmark_command::take_flag(args, RECURSIVE);
         if !args.is_empty() {
-            return Err(CommandErr::Usage(
-                "apply should be called without any arguments".into(),
-            ));
+            return Err(CommandErr::Usage(format!(
+                "apply should be called without any arguments other than {RECURSIVE}"
+            )));
         }
 
         let categories = categories.read();
@@ -23,9 +27,12 @@ pub fn build(
             )
             .expect("failed to get selected category");
 
-        category
-            .apply(&mut bookmarks.write())
-            .map_err(|err| CommandErr::Execution(format!("{err}")))?;
+        if recursive {
+            category.apply_recursive(&categories.storage, &mut bookmarks.write())
+        } else {
+            category.apply(&mut bookmarks.write())
+        }
+        .map_err(|err| CommandErr::Execution(format!("{err}")))?;
         Ok(())
     })
 }
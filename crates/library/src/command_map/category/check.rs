@@ -0,0 +1,27 @@
+use crate::{category::Category, category_check, info::Info, shared};
+use bookmark_command::CommandErr;
+
+pub fn build(
+    categories: shared::BufferStorage<Category>,
+    infos: shared::BufferStorage<Info>,
+) -> Box<dyn bookmark_command::Command> {
+    Box::new(move |args: &[_]| {
+        if !args.is_empty() {
+            return Err(CommandErr::Usage(
+                "check should be called without any arguments".into(),
+            ));
+        }
+
+        let issues =
+            category_check::check_categories(&categories.read().storage, &infos.read().storage);
+
+        if issues.is_empty() {
+            println!("no issues found");
+        }
+        for issue in &issues {
+            println!("{issue}");
+        }
+
+        Ok(())
+    })
+}
@@ -0,0 +1,118 @@
+use crate::{command_map::Builder as CommandMapBuilder, container::SetOperation, shared};
+use bookmark_command::{Command, CommandErr};
+
+/// Push commands for every set operation, each combining the current buffer with named buffers.
+pub fn push_operations<T>(
+    builder: CommandMapBuilder<'static>,
+    buffer_storage: &shared::BufferStorage<T>,
+) -> CommandMapBuilder<'static>
+where
+    T: 'static,
+{
+    [
+        (
+            "union",
+            SetOperation::Union,
+            "replace the buffer by items in it or in any of the named buffers",
+        ),
+        (
+            "intersection",
+            SetOperation::Intersection,
+            "replace the buffer by items in it and in all of the named buffers",
+        ),
+        (
+            "difference",
+            SetOperation::Difference,
+            "replace the buffer by items in it but in none of the named buffers",
+        ),
+        (
+            "symmetric-difference",
+            SetOperation::SymmetricDifference,
+            "replace the buffer by items in either it or a named buffer but not both, for each named buffer in turn",
+        ),
+    ]
+    .into_iter()
+    .fold(builder, |builder, (name, operation, help)| {
+        builder.push(
+            name,
+            Some(&format!("{help}\nusage: {name} NAME [NAME ...]")),
+            build(name, operation, buffer_storage.clone()),
+        )
+    })
+}
+
+pub fn build<T>(
+    name: &'static str,
+    operation: SetOperation,
+    buffer_storage: shared::BufferStorage<T>,
+) -> Box<dyn Command>
+where
+    T: 'static,
+{
+    Box::new(move |args: &[String]| {
+        if args.is_empty() {
+            return Err(CommandErr::Usage(format!(
+                "{name} should be called with at least one buffer name"
+            )));
+        }
+
+        let mut buffer_storage = buffer_storage.write();
+        let len = buffer_storage.storage.len();
+
+        let mut combined = buffer_storage.buffer.clone();
+        for buffer_name in args {
+            let other = buffer_storage
+                .named_buffer(buffer_name)
+                .ok_or_else(|| CommandErr::Execution(format!("no buffer named {buffer_name}")))?;
+            combined = combined.combine(other, operation, len);
+        }
+
+        buffer_storage.replace_buffer(combined);
+
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Bookmark;
+
+    #[test]
+    pub fn combine_named() {
+        let bookmarks = shared::BufferStorage::<Bookmark>::default();
+        bookmarks.write().storage.extend(
+            [("a", "rust"), ("b", "rust"), ("c", "go"), ("d", "")].map(|(url, tag)| {
+                Bookmark::new(url, "", [tag].into_iter().filter(|t| !t.is_empty()))
+            }),
+        );
+        let visible = || bookmarks.read().buffer.iter().take(4).collect::<Vec<_>>();
+
+        let mut write = bookmarks.write();
+        write.filter_in_place(|bookmark| bookmark.tags().any(|t| t == "rust"));
+        write.name_buffer("rust");
+        write.reset();
+        write.filter_in_place(|bookmark| bookmark.url() != "b");
+        write.name_buffer("not-b");
+        write.reset();
+        drop(write);
+
+        let mut difference = build("difference", SetOperation::Difference, bookmarks.clone());
+        let mut union = build("union", SetOperation::Union, bookmarks.clone());
+
+        difference
+            .call(&["rust".into()])
+            .expect("buffer should exist");
+        assert_eq!(visible(), [2, 3]);
+        union
+            .call(&["rust".into(), "not-b".into()])
+            .expect("buffers should exist");
+        assert_eq!(visible(), [2, 3, 0, 1]);
+
+        assert!(union.call(&["missing".into()]).is_err());
+        assert!(union.call(&[]).is_err());
+
+        bookmarks.write().pop_history();
+        assert_eq!(visible(), [2, 3]);
+    }
+}
@@ -0,0 +1,74 @@
+use crate::shared;
+use bookmark_command::{args_are_empty, Command, CommandErr};
+use bookmark_storage::Storeable;
+
+#[derive(Debug, Command)]
+pub struct Compact<T> {
+    buffer_storage: shared::BufferStorage<T>,
+}
+
+impl<T> Command for Compact<T>
+where
+    T: Storeable,
+{
+    fn call(&mut self, args: &[String]) -> Result<(), CommandErr> {
+        args_are_empty(args)?;
+
+        let mut buffer_storage = self.buffer_storage.write();
+
+        let mut compacted = 0;
+        let mut reclaimed = 0;
+        for item in buffer_storage
+            .storage
+            .iter_mut()
+            .filter(|item| item.is_edited())
+        {
+            // unedited items share the loaded content, compacting them would only copy it
+            let stale = item.stale();
+            if stale > 0 {
+                item.compact();
+                compacted += 1;
+                reclaimed += stale;
+            }
+        }
+
+        println!("compacted {compacted} items, reclaiming {reclaimed} bytes");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Bookmark;
+
+    #[test]
+    pub fn compact_edited() {
+        let bookmarks = shared::BufferStorage::<Bookmark>::default();
+        bookmarks.write().storage.extend(
+            ["<url> a <info> b <tag> c", "<url> d <info> e <tag> f"]
+                .map(|line| Bookmark::from_string(line, None).expect("line should parse")),
+        );
+        bookmarks.write().storage[1].set_description("g");
+        let stored = || {
+            bookmarks
+                .read()
+                .storage
+                .iter()
+                .map(Storeable::stored_len)
+                .collect::<Vec<_>>()
+        };
+        let before = stored();
+
+        let mut compact = Compact::build(bookmarks.clone());
+        compact.call(&[]).expect("compact should succeed");
+        let after = stored();
+        assert_eq!(after[0], before[0]);
+        assert_eq!(after[1], 3);
+        assert_eq!(bookmarks.read().storage[1].description(), "g");
+
+        compact.call(&[]).expect("compact should succeed");
+        assert_eq!(stored(), after);
+    }
+}
@@ -0,0 +1,182 @@
+use crate::{
+    container::{self, Change, Identify},
+    shared,
+};
+use bookmark_command::{Command, CommandErr};
+use bookmark_storage::Storeable;
+
+/// Flag used to delete the buffer rather than only showing what would be deleted.
+const YES: &str = "--yes";
+
+/// Record the deletion of items such that it may be undone, along with a change undoing anything
+/// else changed because of the deletion.
+fn record<T>(
+    buffer_storage: &mut container::BufferStorage<T>,
+    description: &str,
+    items: Vec<(usize, T)>,
+    change: Option<Change<T>>,
+) {
+    let change = match change {
+        Some(change) => Change::Many(vec![change, Change::Insert(items)]),
+        None => Change::Insert(items),
+    };
+    buffer_storage.record(description, change);
+}
+
+/// Build a command deleting the selected item or an item given by index or id, `removed` is called
+/// with the removed items and their indices after they have been removed and may return a change
+/// undoing anything else it changed. The deletion is recorded such that it may be undone.
+pub fn build<T>(
+    buffer_storage: shared::BufferStorage<T>,
+    mut removed: impl FnMut(&mut container::BufferStorage<T>, &[(usize, T)]) -> Option<Change<T>>
+        + 'static,
+) -> Box<dyn Command>
+where
+    T: Storeable + Identify + std::fmt::Display + 'static,
+{
+    Box::new(move |args: &[String]| {
+        let mut buffer_storage = buffer_storage.write();
+
+        let index = match args {
+            [] => buffer_storage
+                .selected
+                .index()
+                .ok_or_else(|| CommandErr::Execution("nothing selected".into()))?,
+            [reference] => buffer_storage.index_of(reference).ok_or_else(|| {
+                CommandErr::Execution(format!("{reference} is not a valid index or id"))
+            })?,
+            _ => {
+                return Err(CommandErr::Usage(
+                    "delete should be called with at most one argument".into(),
+                ))
+            }
+        };
+
+        let items = buffer_storage.remove_indexed([index]);
+        for (index, item) in &items {
+            println!("deleted:\n{index}. {item:#}");
+        }
+        let change = removed(&mut buffer_storage, &items);
+        record(&mut buffer_storage, "delete", items, change);
+
+        Ok(())
+    })
+}
+
+/// Build a command deleting every item in the buffer, unless [YES] is given only the amount of items
+/// that would be deleted is shown. `removed` is called like for [build] and the deletion is recorded
+/// such that it may be undone.
+pub fn build_buffer<T>(
+    buffer_storage: shared::BufferStorage<T>,
+    mut removed: impl FnMut(&mut container::BufferStorage<T>, &[(usize, T)]) -> Option<Change<T>>
+        + 'static,
+) -> Box<dyn Command>
+where
+    T: 'static,
+{
+    Box::new(move |args: &[String]| {
+        let (yes, args) = bookmark_command::take_flag(args, YES);
+        if !args.is_empty() {
+            return Err(CommandErr::Usage(format!(
+                "delete-buffer should be called with no arguments other than {YES}"
+            )));
+        }
+
+        let mut buffer_storage = buffer_storage.write();
+        let indices = buffer_storage
+            .iter_indexed()
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+
+        if !yes {
+            println!(
+                "{} of {} items would be deleted, use \"delete-buffer {YES}\" to delete them",
+                indices.len(),
+                buffer_storage.storage.len()
+            );
+            return Ok(());
+        }
+
+        let items = buffer_storage.remove_indexed(indices);
+        println!("deleted {} items", items.len());
+        let change = removed(&mut buffer_storage, &items);
+        record(&mut buffer_storage, "delete-buffer", items, change);
+
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Bookmark;
+
+    #[test]
+    pub fn delete_keeps_indices() {
+        let bookmarks = shared::BufferStorage::<Bookmark>::default();
+        bookmarks.write().storage.extend(
+            ["a", "b1", "c", "d1", "e"]
+                .map(|url| Bookmark::new(url, "", std::iter::empty::<&str>())),
+        );
+        let urls = |indices: Vec<usize>| {
+            let bookmarks = bookmarks.read();
+            indices
+                .into_iter()
+                .map(|index| bookmarks.storage[index].url().to_string())
+                .collect::<Vec<_>>()
+        };
+        let visible = || {
+            let indices = bookmarks
+                .read()
+                .iter_indexed()
+                .map(|(index, _)| index)
+                .collect();
+            urls(indices)
+        };
+
+        let mut delete = build(bookmarks.clone(), |_, _| None);
+        let mut delete_buffer = build_buffer(bookmarks.clone(), |_, _| None);
+
+        {
+            let mut bookmarks = bookmarks.write();
+            bookmarks.filter_in_place(|bookmark| bookmark.url() != "c");
+            bookmarks.name_buffer("not-c");
+            bookmarks.filter_in_place(|bookmark| bookmark.url() != "a");
+            bookmarks.selected.replace(4);
+        }
+
+        delete.call(&["0".into()]).expect("index should exist");
+        assert_eq!(visible(), ["b1", "d1", "e"]);
+        assert_eq!(bookmarks.read().selected.index(), Some(3));
+
+        delete.call(&[]).expect("selected item should exist");
+        assert_eq!(visible(), ["b1", "d1"]);
+        assert!(delete.call(&[]).is_err());
+
+        bookmarks
+            .write()
+            .filter_in_place(|bookmark| bookmark.url().ends_with('1'));
+        delete_buffer.call(&[]).expect("preview should succeed");
+        assert_eq!(bookmarks.read().storage.len(), 3);
+        delete_buffer
+            .call(&[YES.into()])
+            .expect("buffer should be deleted");
+        assert_eq!(bookmarks.read().storage.len(), 1);
+        assert!(visible().is_empty());
+
+        bookmarks.write().pop_history();
+        assert!(visible().is_empty());
+        assert!(bookmarks.write().recall_buffer("not-c"));
+        assert!(visible().is_empty());
+        bookmarks.write().reset();
+        assert_eq!(visible(), ["c"]);
+
+        assert_eq!(bookmarks.write().undo().as_deref(), Some("delete-buffer"));
+        assert_eq!(visible(), ["b1", "c", "d1"]);
+        assert_eq!(bookmarks.write().undo().as_deref(), Some("delete"));
+        assert_eq!(visible(), ["b1", "c", "d1", "e"]);
+        assert_eq!(bookmarks.read().selected.index(), None);
+        assert_eq!(bookmarks.write().redo().as_deref(), Some("delete"));
+        assert_eq!(visible(), ["b1", "c", "d1"]);
+    }
+}
@@ -0,0 +1,194 @@
+use crate::{command_map::Builder as CommandMapBuilder, shared};
+use bookmark_command::{Command, CommandErr};
+use bookmark_storage::Storeable;
+
+/// Push commands for returning to previous buffers and for naming and recalling buffers.
+pub fn push_commands<T>(
+    builder: CommandMapBuilder<'static>,
+    buffer_storage: &shared::BufferStorage<T>,
+) -> CommandMapBuilder<'static>
+where
+    T: Storeable + 'static,
+{
+    builder
+        .push(
+            "pop",
+            Some("return to the buffer used before the last filter\nusage: pop"),
+            Pop::build(buffer_storage.clone()),
+        )
+        .push(
+            "undo-filter",
+            Some("return to the buffer used before the last filter, same as pop\nusage: undo-filter"),
+            Pop::build(buffer_storage.clone()),
+        )
+        .push(
+            "name-buffer",
+            Some("save the current buffer under a name\nusage: name-buffer NAME"),
+            Name::build(buffer_storage.clone()),
+        )
+        .push(
+            "recall-buffer",
+            Some("replace the current buffer by one saved under a name\nusage: recall-buffer NAME"),
+            Recall::build(buffer_storage.clone()),
+        )
+        .push(
+            "buffers",
+            Some("show the amount of buffers in history and the names of saved buffers\nusage: buffers"),
+            Buffers::build(buffer_storage.clone()),
+        )
+}
+
+#[derive(Debug, Command)]
+pub struct Pop<T> {
+    buffer_storage: shared::BufferStorage<T>,
+}
+
+impl<T> Command for Pop<T>
+where
+    T: Storeable,
+{
+    fn call(&mut self, args: &[String]) -> Result<(), CommandErr> {
+        if !args.is_empty() {
+            return Err(CommandErr::Usage(
+                "pop should be used without any arguments".into(),
+            ));
+        }
+
+        let mut buffer_storage = self.buffer_storage.write();
+        if !buffer_storage.pop_history() {
+            return Err(CommandErr::Execution("no previous buffer".into()));
+        }
+
+        println!(
+            "returned to previous buffer, {} remaining in history",
+            buffer_storage.history_len()
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Command)]
+pub struct Name<T> {
+    buffer_storage: shared::BufferStorage<T>,
+}
+
+impl<T> Command for Name<T>
+where
+    T: Storeable,
+{
+    fn call(&mut self, args: &[String]) -> Result<(), CommandErr> {
+        if args.len() != 1 {
+            return Err(CommandErr::Usage(
+                "name-buffer should be called with one argument".into(),
+            ));
+        }
+
+        self.buffer_storage.write().name_buffer(&args[0]);
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Command)]
+pub struct Recall<T> {
+    buffer_storage: shared::BufferStorage<T>,
+}
+
+impl<T> Command for Recall<T>
+where
+    T: Storeable,
+{
+    fn call(&mut self, args: &[String]) -> Result<(), CommandErr> {
+        if args.len() != 1 {
+            return Err(CommandErr::Usage(
+                "recall-buffer should be called with one argument".into(),
+            ));
+        }
+
+        if !self.buffer_storage.write().recall_buffer(&args[0]) {
+            return Err(CommandErr::Execution(format!(
+                "no buffer named {}",
+                &args[0]
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Command)]
+pub struct Buffers<T> {
+    buffer_storage: shared::BufferStorage<T>,
+}
+
+impl<T> Command for Buffers<T>
+where
+    T: Storeable,
+{
+    fn call(&mut self, args: &[String]) -> Result<(), CommandErr> {
+        if !args.is_empty() {
+            return Err(CommandErr::Usage(
+                "buffers should be used without any arguments".into(),
+            ));
+        }
+
+        let buffer_storage = self.buffer_storage.read();
+        let mut names = buffer_storage.buffer_names().collect::<Vec<_>>();
+        names.sort_unstable();
+
+        println!("in history: {}", buffer_storage.history_len());
+        for name in names {
+            println!("- {name}");
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Bookmark;
+
+    #[test]
+    pub fn pop_and_recall() {
+        let bookmarks = shared::BufferStorage::<Bookmark>::default();
+        bookmarks.write().storage.extend(
+            (0..10).map(|i| {
+                Bookmark::new(&format!("https://{i}.com"), "", std::iter::empty::<&str>())
+            }),
+        );
+        let visible = || {
+            let bookmarks = bookmarks.read();
+            let count = bookmarks.buffer.count().unwrap_or(bookmarks.storage.len());
+            bookmarks.buffer.iter().take(count).collect::<Vec<_>>()
+        };
+
+        let mut pop = Pop::build(bookmarks.clone());
+        let mut name = Name::build(bookmarks.clone());
+        let mut recall = Recall::build(bookmarks.clone());
+
+        bookmarks
+            .write()
+            .filter_in_place(|bookmark| bookmark.url().contains('1'));
+        name.call(&["one".into()]).expect("buffer should be named");
+        bookmarks
+            .write()
+            .filter_in_place(|bookmark| bookmark.url().contains('2'));
+        assert!(visible().is_empty());
+
+        pop.call(&[]).expect("there should be a previous buffer");
+        assert_eq!(visible(), [1]);
+        pop.call(&[]).expect("there should be a previous buffer");
+        assert_eq!(visible().len(), 10);
+        assert!(pop.call(&[]).is_err());
+
+        recall.call(&["one".into()]).expect("buffer should exist");
+        assert_eq!(visible(), [1]);
+        assert!(recall.call(&["two".into()]).is_err());
+        pop.call(&[])
+            .expect("recall should keep the previous buffer");
+        assert_eq!(visible().len(), 10);
+    }
+}
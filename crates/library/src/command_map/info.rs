@@ -4,12 +4,14 @@ use std::{
     rc::{Rc, Weak},
 };
 
-use bookmark_command::args_are_empty;
+use bookmark_command::{args_are_empty, Command, CommandErr};
 
 use crate::{
+    bookmark::Bookmark,
     category::Category,
-    command_map::{load, Builder as CommandMapBuilder, CommandMap},
+    command_map::{compact, journal, journaled, load, Builder as CommandMapBuilder, Scope},
     info::Info,
+    membership::{category_membership, Membership},
     shared,
 };
 
@@ -47,17 +49,101 @@ impl CatMap {
     }
 }
 
+fn membership(
+    categories: &shared::BufferStorage<Category>,
+    bookmarks: &shared::BufferStorage<Bookmark>,
+) -> Result<Membership, CommandErr> {
+    category_membership(&categories.read().storage, &bookmarks.read().storage)
+        .map_err(|err| CommandErr::Execution(format!("{err}")))
+}
+
+/// Push commands for finding bookmarks no category matches and for showing how bookmarks are
+/// spread among categories.
+fn push_coverage(
+    builder: CommandMapBuilder<'static>,
+    categories: &shared::BufferStorage<Category>,
+    bookmarks: shared::BufferStorage<Bookmark>,
+) -> CommandMapBuilder<'static> {
+    builder
+        .push(
+            "uncategorized",
+            Some("filter bookmarks in buffer by not being matched by any category\nusage: uncategorized"),
+            {
+                let categories = categories.clone();
+                let bookmarks = bookmarks.clone();
+                Box::new(move |args: &[_]| {
+                    args_are_empty(args)?;
+
+                    let membership = membership(&categories, &bookmarks)?;
+                    bookmarks.write().filter_indices_in_place(|i| {
+                        membership.categories_of(*i).is_empty()
+                    });
+
+                    Ok(())
+                })
+            },
+        )
+        .push(
+            "coverage",
+            Some("show how many bookmarks each category matches, how many bookmarks pairs of categories share and how many bookmarks no category matches\nusage: coverage"),
+            {
+                let categories = categories.clone();
+                Box::new(move |args: &[_]| {
+                    args_are_empty(args)?;
+
+                    let membership = membership(&categories, &bookmarks)?;
+                    let categories = categories.read();
+                    let name = |i: usize| categories.storage[i].name();
+
+                    println!("matches:");
+                    for (i, count) in membership.match_counts().into_iter().enumerate() {
+                        println!("\t{}: {count}", name(i));
+                    }
+
+                    println!("overlaps:");
+                    for ((first, second), count) in membership.overlap_counts() {
+                        println!("\t{} & {}: {count}", name(first), name(second));
+                    }
+
+                    println!(
+                        "uncategorized: {} of {}",
+                        membership.uncategorized().count(),
+                        membership.bookmark_count()
+                    );
+
+                    Ok(())
+                })
+            },
+        )
+}
+
 pub fn build(
     name: String,
     infos: shared::BufferStorage<Info>,
     categories: shared::BufferStorage<Category>,
-) -> Box<CommandMap<'static>> {
+    bookmarks: shared::BufferStorage<Bookmark>,
+) -> Box<dyn Command> {
+    let builder = push_coverage(CommandMapBuilder::new().name(name), &categories, bookmarks);
+    let builder = journal::push_commands(builder, &infos);
+
     Box::new(
-        CommandMapBuilder::new()
-            .name(name)
-            .push("load", None, load::Load::build(infos.clone()))
+        builder
+            .push(
+                "load",
+                None,
+                journaled(
+                    "load",
+                    Scope::Appended,
+                    load::Load::build(infos.clone()),
+                    infos.clone(),
+                ),
+            )
+            .push(
+                "compact",
+                Some("remove content no longer used by info"),
+                compact::Compact::build(infos.clone()),
+            )
             .push("categories", Some("show category hierarchy"), {
-                let categories = categories;
                 let infos = infos.clone();
                 Box::new(move |args: &[_]| {
                     args_are_empty(args)?;
@@ -109,7 +195,6 @@ pub fn build(
                 })
             })
             .push("show", None, {
-                let infos = infos;
                 Box::new(move |args: &[_]| {
                     if !args.is_empty() {
                         return Err(bookmark_command::CommandErr::Execution(
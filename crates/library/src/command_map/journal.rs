@@ -0,0 +1,393 @@
+use crate::{
+    bookmark::Bookmark,
+    category::Category,
+    command_map::Builder as CommandMapBuilder,
+    container::{Change, SharedChange},
+    info::Info,
+    shared,
+};
+use bookmark_command::{Command, CommandErr};
+use bookmark_storage::Storeable;
+
+/// What part of a [Storage](crate::container::Storage) a command may change, decides what is kept to undo the command.
+/// Commands changing any other part record their changes themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    /// Only the selected item is changed.
+    Selected,
+    /// Items are only added to the end.
+    Appended,
+}
+
+enum Before<T> {
+    Selected(Option<(usize, T)>),
+    Appended(usize),
+}
+
+fn changed<T: Storeable>(before: &T, after: &T) -> bool {
+    before.to_line() != after.to_line()
+}
+
+/// Wrap a command changing a [Storage](crate::container::Storage) such that its changes are recorded and may be undone, the
+/// changes are recorded even if the command fails since it may have failed part way through.
+pub fn journaled<T>(
+    description: &'static str,
+    scope: Scope,
+    mut command: Box<dyn Command>,
+    buffer_storage: shared::BufferStorage<T>,
+) -> Box<dyn Command>
+where
+    T: Storeable + Clone + 'static,
+{
+    Box::new(move |args: &[String]| {
+        let before = {
+            let buffer_storage = buffer_storage.read();
+            match scope {
+                Scope::Selected => Before::Selected(
+                    buffer_storage
+                        .get_index_and_selected_and()
+                        .ok()
+                        .map(|(index, item)| (index, item.clone())),
+                ),
+                Scope::Appended => Before::Appended(buffer_storage.storage.len()),
+            }
+        };
+
+        let result = command.call(args);
+
+        let mut buffer_storage = buffer_storage.write();
+        let change = match before {
+            Before::Selected(None) => None,
+            Before::Selected(Some((index, item))) => buffer_storage
+                .storage
+                .get(index)
+                .is_some_and(|current| changed(&item, current))
+                .then(|| Change::Replace(vec![(index, item)])),
+            Before::Appended(len) => {
+                (buffer_storage.storage.len() > len).then_some(Change::Truncate(len))
+            }
+        };
+
+        if let Some(change) = change {
+            buffer_storage.record(description, change);
+        }
+
+        result
+    })
+}
+
+/// Lengths of the bookmark, category and info storages, taken before a file is loaded into them
+/// such that everything the load appended may be recorded as a single change.
+#[derive(Debug, Clone, Copy)]
+pub struct Lengths {
+    bookmarks: usize,
+    categories: usize,
+    infos: usize,
+}
+
+impl Lengths {
+    /// Take the current lengths of the storages.
+    #[must_use]
+    pub fn of(
+        bookmarks: &shared::BufferStorage<Bookmark>,
+        categories: &shared::BufferStorage<Category>,
+        infos: &shared::BufferStorage<Info>,
+    ) -> Self {
+        Self {
+            bookmarks: bookmarks.read().storage.len(),
+            categories: categories.read().storage.len(),
+            infos: infos.read().storage.len(),
+        }
+    }
+
+    /// Record the items appended since the lengths were taken as a single change to the
+    /// bookmarks, undoing it removes the appended items from all three storages. Nothing is
+    /// recorded if nothing was appended.
+    pub fn record_appended(
+        self,
+        description: &str,
+        bookmarks: &shared::BufferStorage<Bookmark>,
+        categories: &shared::BufferStorage<Category>,
+        infos: &shared::BufferStorage<Info>,
+    ) {
+        let mut changes = Vec::new();
+        if categories.read().storage.len() > self.categories {
+            changes.push(Change::Linked(Box::new(SharedChange {
+                buffer_storage: categories.clone(),
+                change: Change::Truncate(self.categories),
+            })));
+        }
+        if infos.read().storage.len() > self.infos {
+            changes.push(Change::Linked(Box::new(SharedChange {
+                buffer_storage: infos.clone(),
+                change: Change::Truncate(self.infos),
+            })));
+        }
+
+        let mut bookmarks = bookmarks.write();
+        if bookmarks.storage.len() > self.bookmarks {
+            changes.push(Change::Truncate(self.bookmarks));
+        }
+
+        let change = match changes.len() {
+            0 => return,
+            1 => changes.remove(0),
+            _ => Change::Many(changes),
+        };
+        bookmarks.record(description, change);
+    }
+}
+
+/// Wrap a command loading a file into bookmarks, categories and infos such that what it appended
+/// is recorded as a single change to the bookmarks, see [`Lengths::record_appended`]. The
+/// changes are recorded even if the command fails since it may have failed part way through.
+pub fn journaled_load(
+    mut command: Box<dyn Command>,
+    bookmarks: shared::BufferStorage<Bookmark>,
+    categories: shared::BufferStorage<Category>,
+    infos: shared::BufferStorage<Info>,
+) -> Box<dyn Command> {
+    Box::new(move |args: &[String]| {
+        let lengths = Lengths::of(&bookmarks, &categories, &infos);
+        let result = command.call(args);
+        lengths.record_appended("load", &bookmarks, &categories, &infos);
+        result
+    })
+}
+
+/// Push commands for undoing and redoing recorded changes and for limiting how many are kept.
+pub fn push_commands<T>(
+    builder: CommandMapBuilder<'static>,
+    buffer_storage: &shared::BufferStorage<T>,
+) -> CommandMapBuilder<'static>
+where
+    T: 'static,
+{
+    let undo = {
+        let buffer_storage = buffer_storage.clone();
+        move |args: &[String]| {
+            if !args.is_empty() {
+                return Err(CommandErr::Usage(
+                    "undo should be used without any arguments".into(),
+                ));
+            }
+            let description = buffer_storage
+                .write()
+                .undo()
+                .ok_or_else(|| CommandErr::Execution("nothing to undo".into()))?;
+            println!("undid {description}");
+            Ok(())
+        }
+    };
+
+    let redo = {
+        let buffer_storage = buffer_storage.clone();
+        move |args: &[String]| {
+            if !args.is_empty() {
+                return Err(CommandErr::Usage(
+                    "redo should be used without any arguments".into(),
+                ));
+            }
+            let description = buffer_storage
+                .write()
+                .redo()
+                .ok_or_else(|| CommandErr::Execution("nothing to redo".into()))?;
+            println!("redid {description}");
+            Ok(())
+        }
+    };
+
+    let limit = {
+        let buffer_storage = buffer_storage.clone();
+        move |args: &[String]| {
+            let mut buffer_storage = buffer_storage.write();
+            match args {
+                [] => (),
+                [limit] => {
+                    let limit = limit.parse().map_err(|_| {
+                        CommandErr::Execution(format!(
+                            "could not parse {limit} as a positive integer"
+                        ))
+                    })?;
+                    buffer_storage.journal_mut().set_limit(limit);
+                }
+                _ => {
+                    return Err(CommandErr::Usage(
+                        "journal-limit should be called with at most one argument".into(),
+                    ))
+                }
+            }
+
+            let journal = buffer_storage.journal();
+            println!(
+                "limit: {}, undoable: {}, redoable: {}",
+                journal.limit(),
+                journal.undo_len(),
+                journal.redo_len()
+            );
+            Ok(())
+        }
+    };
+
+    builder
+        .push(
+            "undo",
+            Some("undo the last change\nusage: undo"),
+            Box::new(undo),
+        )
+        .push(
+            "redo",
+            Some("redo the last undone change\nusage: redo"),
+            Box::new(redo),
+        )
+        .push(
+            "journal-limit",
+            Some(
+                "show or set how many changes are kept to be undone\nusage: journal-limit [LIMIT]",
+            ),
+            Box::new(limit),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Bookmark;
+
+    #[test]
+    pub fn undo_and_redo() {
+        let bookmarks = shared::BufferStorage::<Bookmark>::default();
+        let urls = || {
+            bookmarks
+                .read()
+                .storage
+                .iter()
+                .map(|bookmark| bookmark.url().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let mut append = journaled(
+            "append",
+            Scope::Appended,
+            Box::new({
+                let bookmarks = bookmarks.clone();
+                move |args: &[String]| {
+                    bookmarks.write().storage.extend(
+                        args.iter()
+                            .map(|url| Bookmark::new(url, "", std::iter::empty::<&str>())),
+                    );
+                    Ok(())
+                }
+            }),
+            bookmarks.clone(),
+        );
+        let reverse = {
+            let bookmarks = bookmarks.clone();
+            move || {
+                let mut bookmarks = bookmarks.write();
+                let order = (0..bookmarks.storage.len()).rev().collect::<Vec<_>>();
+                let inverse = bookmarks.permute(&order);
+                bookmarks.record("reverse", Change::Permute(inverse));
+            }
+        };
+        let mut set = journaled(
+            "set",
+            Scope::Selected,
+            Box::new({
+                let bookmarks = bookmarks.clone();
+                move |args: &[String]| {
+                    let mut bookmarks = bookmarks.write();
+                    let (_, bookmark) = bookmarks
+                        .get_index_and_selected_mut()
+                        .map_err(|err| CommandErr::Execution(err.to_string()))?;
+                    bookmark.set_url(&args[0]);
+                    Ok(())
+                }
+            }),
+            bookmarks.clone(),
+        );
+
+        append
+            .call(&["a".into(), "b".into()])
+            .expect("append should succeed");
+        reverse();
+        bookmarks.write().selected.replace(0);
+        set.call(&["c".into()]).expect("set should succeed");
+        assert!(set.call(&["c".into()]).is_ok());
+        assert_eq!(urls(), ["c", "a"]);
+
+        let undo = || bookmarks.write().undo();
+        assert_eq!(undo().as_deref(), Some("set"));
+        assert_eq!(urls(), ["b", "a"]);
+        assert_eq!(undo().as_deref(), Some("reverse"));
+        assert_eq!(urls(), ["a", "b"]);
+        assert_eq!(undo().as_deref(), Some("append"));
+        assert!(urls().is_empty());
+        assert_eq!(undo(), None);
+
+        assert_eq!(bookmarks.write().redo().as_deref(), Some("append"));
+        assert_eq!(bookmarks.write().redo().as_deref(), Some("reverse"));
+        assert_eq!(urls(), ["b", "a"]);
+
+        append.call(&["d".into()]).expect("append should succeed");
+        assert_eq!(bookmarks.write().redo(), None);
+
+        bookmarks.write().journal_mut().set_limit(1);
+        assert_eq!(undo().as_deref(), Some("append"));
+        assert_eq!(undo(), None);
+        assert_eq!(urls(), ["b", "a"]);
+    }
+
+    #[test]
+    pub fn load_as_one_change() {
+        let bookmarks = shared::BufferStorage::<Bookmark>::default();
+        let categories = shared::BufferStorage::<Category>::default();
+        let infos = shared::BufferStorage::<Info>::default();
+        let lens = || {
+            [
+                bookmarks.read().storage.len(),
+                categories.read().storage.len(),
+                infos.read().storage.len(),
+            ]
+        };
+
+        let mut load = journaled_load(
+            Box::new({
+                let (bookmarks, categories, infos) =
+                    (bookmarks.clone(), categories.clone(), infos.clone());
+                move |_: &[String]| {
+                    bookmarks.write().storage.extend([
+                        Bookmark::new("a", "", std::iter::empty::<&str>()),
+                        Bookmark::new("b", "", std::iter::empty::<&str>()),
+                    ]);
+                    categories.write().storage.push(Category::new(
+                        "c",
+                        "c",
+                        "",
+                        std::iter::empty::<&str>(),
+                        std::iter::empty::<&str>(),
+                    ));
+                    infos.write().storage.push(Info::new(
+                        std::iter::empty::<&str>(),
+                        std::iter::empty::<&str>(),
+                    ));
+                    Ok(())
+                }
+            }),
+            bookmarks.clone(),
+            categories.clone(),
+            infos.clone(),
+        );
+
+        load.call(&[]).expect("load should succeed");
+        load.call(&[]).expect("load should succeed");
+        assert_eq!(lens(), [4, 2, 2]);
+
+        assert_eq!(bookmarks.write().undo().as_deref(), Some("load"));
+        assert_eq!(lens(), [2, 1, 1]);
+        assert_eq!(bookmarks.write().undo().as_deref(), Some("load"));
+        assert_eq!(lens(), [0, 0, 0]);
+        assert_eq!(bookmarks.write().redo().as_deref(), Some("load"));
+        assert_eq!(lens(), [2, 1, 1]);
+    }
+}
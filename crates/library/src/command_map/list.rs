@@ -32,7 +32,7 @@ where
     fn call(&mut self, args: &[String]) -> Result<(), CommandErr> {
         let buffer_storage = self.buffer_storage.read();
         let count = args
-            .get(0)
+            .first()
             .map_or_else(
                 || {
                     Ok(buffer_storage
@@ -1,10 +1,73 @@
-use crate::{bookmark::Bookmark, category::Category, info::Info, shared};
+use crate::{bookmark::Bookmark, category::Category, format, info::Info, shared};
 use bookmark_command::{Command, CommandErr};
-use bookmark_storage::Listed;
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use bookmark_storage::{Collector, Listed, ParseErr, Quarantined};
+use std::{fs, sync::Arc};
+
+/// Flag making loads skip and report lines that cannot be parsed.
+const LENIENT: &str = "--lenient";
+
+/// Option setting the amount of threads lines are parsed on.
+const THREADS: &str = "--threads";
+
+/// Options shared by load commands.
+struct Options {
+    lenient: bool,
+    threads: usize,
+}
+
+impl Options {
+    /// Remove load options from arguments, ensuring only a path remains.
+    fn take(args: &[String]) -> Result<(Self, String), CommandErr> {
+        let (lenient, args) = bookmark_command::take_flag(args, LENIENT);
+        let (threads, args) = bookmark_command::take_option(&args, THREADS)?;
+
+        let threads = match threads {
+            Some(threads) => threads
+                .parse()
+                .ok()
+                .filter(|threads| *threads > 0)
+                .ok_or_else(|| {
+                    CommandErr::Usage(format!("{THREADS} should be a positive integer"))
+                })?,
+            None => 1,
+        };
+
+        let [path] = <[String; 1]>::try_from(args)
+            .map_err(|_| CommandErr::Execution("load should be called with one argument".into()))?;
+
+        Ok((Self { lenient, threads }, path))
+    }
+
+    fn collector<T>(&self) -> Collector<T> {
+        Collector::new(self.lenient).with_threads(self.threads)
+    }
+}
+
+fn print_quarantined(quarantined: &[Quarantined]) {
+    for line in quarantined {
+        println!("quarantined: {line}");
+    }
+}
+
+/// Give loaded bookmarks reusing an id a new one, printing a warning for each.
+fn reassign_duplicate_ids(bookmarks: &mut [Bookmark], from: usize) {
+    for reassigned in Bookmark::reassign_duplicate_ids(bookmarks, from) {
+        println!("warning: {reassigned}");
+    }
+}
+
+/// Wrap a command loading bookmarks such that loaded bookmarks reusing an id are given a new one.
+pub fn unique_ids(
+    mut command: Box<dyn Command>,
+    bookmarks: shared::BufferStorage<Bookmark>,
+) -> Box<dyn Command> {
+    Box::new(move |args: &[String]| {
+        let from = bookmarks.read().storage.len();
+        let result = command.call(args);
+        reassign_duplicate_ids(&mut bookmarks.write().storage, from);
+        result
+    })
+}
 
 #[derive(Debug, Command)]
 pub struct Load<T> {
@@ -13,27 +76,34 @@ pub struct Load<T> {
 
 impl<T> Command for Load<T>
 where
-    T: Listed,
+    T: Listed + Send,
 {
     fn call(&mut self, args: &[String]) -> Result<(), CommandErr> {
-        if args.len() != 1 {
-            return Err(CommandErr::Execution(
-                "load should be called with one argument".into(),
-            ));
-        }
+        let (options, path) = Options::take(args)?;
+
+        let content = Arc::<str>::from(fs::read_to_string(&path)?);
+
+        let mut collector = options.collector::<T>();
+
+        bookmark_storage::scan_shared(&content, &format::MIGRATIONS, &mut [&mut collector])
+            .map_err(|err| err.with_path(&path))?;
 
-        let loaded = bookmark_storage::load(&mut File::open(&args[0])?)?;
+        let report = collector
+            .into_report()
+            .map_err(|err| err.with_path(&path))?
+            .with_path(&path);
 
-        if loaded.is_empty() {
+        print_quarantined(&report.quarantined);
+
+        if report.items.is_empty() {
             return Err(CommandErr::Execution(format!(
-                "no lines parsed from {}",
-                &args[0]
+                "no lines parsed from {path}"
             )));
         }
 
         let mut buffer_storage = self.buffer_storage.write();
 
-        buffer_storage.storage.extend(loaded.into_iter());
+        buffer_storage.storage.extend(report.items);
         buffer_storage.buffer.reset();
 
         Ok(())
@@ -47,35 +117,188 @@ pub struct All {
     infos: shared::BufferStorage<Info>,
 }
 
-macro_rules! load_section {
-    ($fmt:expr, $dest:expr, $source:expr) => {{
-        let mut dest = $dest.write();
-        let loaded = bookmark_storage::load_from($source.by_ref())?;
+impl Command for All {
+    fn call(&mut self, args: &[String]) -> Result<(), CommandErr> {
+        let (options, path) = Options::take(args)?;
+
+        let content = Arc::<str>::from(fs::read_to_string(&path)?);
 
-        println!($fmt, loaded.len());
+        let mut infos = options.collector::<Info>();
+        let mut categories = options.collector::<Category>();
+        let mut bookmarks = options.collector::<Bookmark>();
 
-        dest.storage.extend(loaded.into_iter());
-        dest.buffer.reset();
-    }};
-}
+        let unknown = bookmark_storage::scan_shared(
+            &content,
+            &format::MIGRATIONS,
+            &mut [&mut infos, &mut categories, &mut bookmarks],
+        )
+        .map_err(|err| err.with_path(&path))?;
 
-impl Command for All {
-    fn call(&mut self, args: &[String]) -> Result<(), CommandErr> {
-        if args.len() != 1 {
-            return Err(CommandErr::Execution(
-                "load should be called with one argument".into(),
-            ));
+        for section in unknown {
+            println!("warning: {}", section.with_path(&path));
         }
 
-        let reader = BufReader::new(File::open(&args[0])?);
-        let mut lines = reader.lines().enumerate();
+        let with_path = |err: ParseErr| err.with_path(&path);
+        let infos = infos.into_report().map_err(with_path)?.with_path(&path);
+        let categories = categories
+            .into_report()
+            .map_err(with_path)?
+            .with_path(&path);
+        let bookmarks = bookmarks.into_report().map_err(with_path)?.with_path(&path);
+
+        macro_rules! extend_storage {
+            ($fmt:expr, $dest:expr, $report:expr) => {{
+                print_quarantined(&$report.quarantined);
+
+                println!($fmt, $report.items.len());
+
+                let mut dest = $dest.write();
+                dest.storage.extend($report.items);
+                dest.buffer.reset();
+            }};
+        }
 
-        load_section!("loaded {} infos", self.infos, lines);
+        extend_storage!("loaded {} infos", self.infos, infos);
 
-        load_section!("loaded {} categories", self.categories, lines);
+        extend_storage!("loaded {} categories", self.categories, categories);
 
-        load_section!("loaded {} bookmarks", self.bookmarks, lines);
+        let from = self.bookmarks.read().storage.len();
+        extend_storage!("loaded {} bookmarks", self.bookmarks, bookmarks);
+        reassign_duplicate_ids(&mut self.bookmarks.write().storage, from);
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::token;
+    use std::io::BufRead;
+
+    #[test]
+    pub fn scan_any_order() {
+        let content = [
+            token::unsorted::BEGIN,
+            "<url> a <info> b <tag> c",
+            token::unsorted::END,
+            "#FUTURE_BEGIN",
+            "<url> not a bookmark",
+            "#FUTURE_END",
+            token::unsorted::BEGIN,
+            "<url> d <info> e <tag> f",
+            token::unsorted::END,
+            token::info::BEGIN,
+            "<category> g <tag> h",
+            token::info::END,
+        ]
+        .join("\n");
+
+        let mut infos = Collector::<Info>::new(false);
+        let mut categories = Collector::<Category>::new(false);
+        let mut bookmarks = Collector::<Bookmark>::new(false);
+
+        let unknown = bookmark_storage::scan(
+            content.as_bytes().lines().enumerate(),
+            &mut [&mut infos, &mut categories, &mut bookmarks],
+        )
+        .expect("scan should succeed");
+
+        assert_eq!(unknown.len(), 1);
+        assert_eq!(unknown[0].line_num, 3);
+        assert_eq!(unknown[0].token, "#FUTURE_BEGIN");
+
+        assert_eq!(bookmarks.sections(), 2);
+        assert_eq!(categories.sections(), 0);
+
+        let bookmarks = bookmarks.into_report().expect("report should be ok").items;
+        assert_eq!(bookmarks.len(), 2);
+        assert_eq!(bookmarks[1].url(), "d");
+        assert_eq!(
+            infos
+                .into_report()
+                .expect("report should be ok")
+                .items
+                .len(),
+            1
+        );
+        assert!(categories
+            .into_report()
+            .expect("report should be ok")
+            .items
+            .is_empty());
+    }
+
+    #[test]
+    pub fn scan_shared() {
+        let lines = [
+            token::unsorted::BEGIN,
+            "<url> a\\\\b <info> b <tag> c<,>d",
+            token::unsorted::END,
+        ];
+
+        let load = |content: String| {
+            let mut bookmarks = Collector::<Bookmark>::new(false);
+            bookmark_storage::scan_shared(
+                &Arc::from(content),
+                &format::MIGRATIONS,
+                &mut [&mut bookmarks],
+            )
+            .expect("scan should succeed");
+            bookmarks.into_report().expect("report should be ok").items
+        };
+
+        let current = load(format!(
+            "{} {}\n{}",
+            bookmark_storage::token::VERSION,
+            format::VERSION,
+            lines.join("\n")
+        ));
+        assert_eq!(current[0].url(), "a\\b");
+        assert_eq!(current[0].tags().collect::<Vec<_>>(), ["c", "d"]);
+
+        let legacy = load(lines.join("\r\n"));
+        assert_eq!(legacy[0].url(), "a\\\\b");
+        assert_eq!(legacy[0].description(), "b");
+    }
+
+    #[test]
+    pub fn parse_parallel() {
+        let mut lines = (0..10)
+            .map(|i| format!("<url> {i} <info> b <tag> c"))
+            .collect::<Vec<_>>();
+        lines[4] = "<url> 4 <tag> c".into();
+        lines[7] = "<url> 7".into();
+
+        let content = Arc::<str>::from(format!(
+            "{}\n{}\n{}",
+            token::unsorted::BEGIN,
+            lines.join("\n"),
+            token::unsorted::END
+        ));
+
+        let load = |lenient: bool| {
+            let mut bookmarks = Collector::<Bookmark>::new(lenient).with_threads(3);
+            bookmark_storage::scan_shared(&content, &format::MIGRATIONS, &mut [&mut bookmarks])
+                .expect("scan should succeed");
+            bookmarks.into_report()
+        };
+
+        let report = load(true).expect("lenient load should succeed");
+        assert_eq!(
+            report.items.iter().map(Bookmark::url).collect::<Vec<_>>(),
+            ["0", "1", "2", "3", "5", "6", "8", "9"]
+        );
+        assert_eq!(
+            report
+                .quarantined
+                .iter()
+                .map(|q| q.line_num)
+                .collect::<Vec<_>>(),
+            [5, 8]
+        );
+
+        let err = load(false).expect_err("strict load should fail");
+        assert_eq!(err.location().and_then(|l| l.line), Some(5));
+    }
+}
@@ -1,7 +1,24 @@
-use crate::{bookmark::Bookmark, category::Category, info::Info, shared};
+use crate::{bookmark::Bookmark, category::Category, format, info::Info, shared};
 use bookmark_command::{Command, CommandErr};
 use bookmark_storage::Listed;
-use std::{fs::File, io::BufWriter};
+
+/// Amount of backups kept when saving if not specified otherwise.
+pub const DEFAULT_BACKUPS: usize = 3;
+
+/// Option used to set the amount of backups kept when saving.
+const BACKUPS: &str = "--backups";
+
+/// Remove the backups option from arguments, giving the amount of backups to keep.
+fn take_backups(args: &[String]) -> Result<(usize, Vec<String>), CommandErr> {
+    let (backups, args) = bookmark_command::take_option(args, BACKUPS)?;
+    let backups = match backups {
+        Some(backups) => backups.parse().map_err(|_| {
+            CommandErr::Usage(format!("{BACKUPS} should be a non-negative integer"))
+        })?,
+        None => DEFAULT_BACKUPS,
+    };
+    Ok((backups, args))
+}
 
 #[derive(Debug, Command)]
 pub struct Save<T> {
@@ -13,6 +30,8 @@ where
     T: Listed,
 {
     fn call(&mut self, args: &[String]) -> Result<(), CommandErr> {
+        let (backups, args) = take_backups(args)?;
+
         if args.len() != 1 {
             return Err(CommandErr::Execution(
                 "save should be called with one argument".into(),
@@ -21,15 +40,18 @@ where
 
         let buffer_storage = self.buffer_storage.write();
 
-        bookmark_storage::save(
-            &mut BufWriter::new(File::create(&args[0])?),
-            buffer_storage
-                .buffer
-                .iter()
-                .map(|i| buffer_storage.storage.get(i))
-                .take_while(Option::is_some)
-                .map(Option::unwrap),
-        )?;
+        bookmark_storage::save_atomic(&args[0], backups, |writer| {
+            bookmark_storage::write_header(writer, format::VERSION)?;
+            bookmark_storage::save(
+                writer,
+                buffer_storage
+                    .buffer
+                    .iter()
+                    .map(|i| buffer_storage.storage.get(i))
+                    .take_while(Option::is_some)
+                    .map(Option::unwrap),
+            )
+        })?;
 
         Ok(())
     }
@@ -46,23 +68,29 @@ impl Command for All {
     fn call(&mut self, args: &[String]) -> Result<(), CommandErr> {
         use bookmark_storage::save;
 
+        let (backups, args) = take_backups(args)?;
+
         if args.len() != 1 {
             return Err(CommandErr::Execution(
                 "save should be called with one argument".into(),
             ));
         }
 
-        let mut writer = BufWriter::new(File::create(&args[0])?);
+        bookmark_storage::save_atomic(&args[0], backups, |writer| {
+            bookmark_storage::write_header(writer, format::VERSION)?;
 
-        macro_rules! save_buffer_storage {
-            ($($storage:expr),* $(,)?) => {
-                $(
-                    save(&mut writer, $storage.read().storage.iter())?;
-                )*
-            };
-        }
+            macro_rules! save_buffer_storage {
+                ($($storage:expr),* $(,)?) => {
+                    $(
+                        save(writer, $storage.read().storage.iter())?;
+                    )*
+                };
+            }
+
+            save_buffer_storage!(self.infos, self.categories, self.bookmarks);
 
-        save_buffer_storage!(self.infos, self.categories, self.bookmarks);
+            Ok(())
+        })?;
 
         Ok(())
     }
@@ -1,4 +1,4 @@
-use crate::shared;
+use crate::{container::Identify, shared};
 use bookmark_command::{Command, CommandErr};
 use bookmark_storage::Storeable;
 
@@ -9,7 +9,7 @@ pub struct Select<T> {
 
 impl<T> Command for Select<T>
 where
-    T: Storeable + std::fmt::Display,
+    T: Storeable + Identify + std::fmt::Display,
 {
     fn call(&mut self, args: &[String]) -> Result<(), CommandErr> {
         if args.len() != 1 {
@@ -18,19 +18,13 @@ where
             ));
         }
 
-        let index = args[0].parse().map_err(|_| {
-            CommandErr::Usage(format!(
-                "could not parse {} as a positive integer",
-                &args[0]
-            ))
-        })?;
-
         let mut buffer_storage = self.buffer_storage.write();
 
-        let selected_item = buffer_storage
-            .storage
-            .get(index)
-            .ok_or_else(|| CommandErr::Execution(format!("{index} is not a valid index")))?;
+        let index = buffer_storage.index_of(&args[0]).ok_or_else(|| {
+            CommandErr::Execution(format!("{} is not a valid index or id", &args[0]))
+        })?;
+
+        let selected_item = &buffer_storage.storage[index];
 
         println!("selected:\n{index}. {selected_item:#}");
         buffer_storage.selected.replace(index);
@@ -2,11 +2,14 @@
 
 mod buffer;
 mod buffer_storage;
+mod journal;
 mod selected;
 mod storage;
 
-pub use buffer::Buffer;
+pub use buffer::{Buffer, SetOperation};
 pub use buffer_storage::BufferStorage;
 pub use buffer_storage::GetSelectedErr;
+pub use buffer_storage::Identify;
+pub use journal::{Change, Journal, Linked, SharedChange, JOURNAL_LIMIT};
 pub use selected::Selected;
 pub use storage::Storage;
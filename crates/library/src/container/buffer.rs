@@ -1,3 +1,18 @@
+use std::collections::HashSet;
+
+/// Set operation used to combine two [Buffer] instances.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetOperation {
+    /// Indices in either buffer.
+    Union,
+    /// Indices in both buffers.
+    Intersection,
+    /// Indices in the first buffer but not in the second.
+    Difference,
+    /// Indices in exactly one of the buffers.
+    SymmetricDifference,
+}
+
 /// A list of indices usefult when applying filters to some index based storage.
 #[derive(Debug, Clone, Default)]
 pub struct Buffer {
@@ -28,6 +43,41 @@ impl Buffer {
         self
     }
 
+    /// Filters the indices in place by applying a condition to the indices themselves, the length
+    /// of the content is needed for buffers representing all items.
+    pub fn filter_indices_in_place(&mut self, len: usize, f: impl FnMut(&usize) -> bool) -> &Self {
+        let filtered = match self.indices.take() {
+            Some(indices) => indices.into_iter().filter(f).collect(),
+            None => (0..len).filter(f).collect(),
+        };
+
+        self.indices.replace(filtered);
+
+        self
+    }
+
+    /// Sort the indices in place by comparing them, a buffer representing all items is first given
+    /// a fixed amount of indices based on the length of the content.
+    pub fn sort_by(
+        &mut self,
+        len: usize,
+        compare: impl FnMut(&usize, &usize) -> std::cmp::Ordering,
+    ) -> &mut Self {
+        self.indices
+            .get_or_insert_with(|| (0..len).collect())
+            .sort_by(compare);
+        self
+    }
+
+    /// Update the indices using a map from old to new indices, indices the map gives none for are
+    /// removed. A buffer representing all items is left as is.
+    pub fn remap(&mut self, map: impl FnMut(usize) -> Option<usize>) -> &mut Self {
+        if let Some(ref mut indices) = self.indices {
+            *indices = indices.iter().copied().filter_map(map).collect();
+        }
+        self
+    }
+
     /// Reset the buffer to a state representing all items being selected.
     pub fn reset(&mut self) -> &mut Self {
         self.indices.take();
@@ -43,4 +93,72 @@ impl Buffer {
             either::Right(0..)
         }
     }
+
+    /// Combine the buffer with another using a set operation, the order of the indices in this
+    /// buffer is kept with indices only in the other buffer following in their order. The length
+    /// of the content is needed for buffers representing all items.
+    #[must_use]
+    pub fn combine(&self, other: &Buffer, operation: SetOperation, len: usize) -> Buffer {
+        let indices = |buffer: &Buffer| match buffer.indices {
+            Some(ref indices) => indices.clone(),
+            None => (0..len).collect(),
+        };
+        let (lhs, rhs) = (indices(self), indices(other));
+        let (in_lhs, in_rhs) = (
+            lhs.iter().copied().collect::<HashSet<_>>(),
+            rhs.iter().copied().collect::<HashSet<_>>(),
+        );
+
+        let from_lhs = |keep_shared: bool| {
+            lhs.iter()
+                .copied()
+                .filter(move |i| in_rhs.contains(i) == keep_shared)
+        };
+        let only_rhs = || rhs.iter().copied().filter(|i| !in_lhs.contains(i));
+
+        let combined = match operation {
+            SetOperation::Union => lhs.iter().copied().chain(only_rhs()).collect(),
+            SetOperation::Intersection => from_lhs(true).collect(),
+            SetOperation::Difference => from_lhs(false).collect(),
+            SetOperation::SymmetricDifference => from_lhs(false).chain(only_rhs()).collect(),
+        };
+
+        Buffer {
+            indices: Some(combined),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn set_operations() {
+        let content = (0..6).collect::<Vec<_>>();
+        let mut odd = Buffer::default();
+        odd.filter_in_place(&content, |i| i % 2 == 1);
+        let mut small = Buffer::default();
+        small.filter_in_place(&content, |i| *i < 4);
+        small.sort_by(content.len(), |a, b| b.cmp(a));
+
+        let combine = |operation| {
+            small
+                .combine(&odd, operation, content.len())
+                .iter()
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(combine(SetOperation::Union), [3, 2, 1, 0, 5]);
+        assert_eq!(combine(SetOperation::Intersection), [3, 1]);
+        assert_eq!(combine(SetOperation::Difference), [2, 0]);
+        assert_eq!(combine(SetOperation::SymmetricDifference), [2, 0, 5]);
+        assert_eq!(
+            Buffer::default()
+                .combine(&odd, SetOperation::Difference, content.len())
+                .iter()
+                .collect::<Vec<_>>(),
+            [0, 2, 4]
+        );
+    }
 }
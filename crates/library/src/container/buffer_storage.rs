@@ -1,4 +1,8 @@
-use super::{Buffer, Selected, Storage};
+use super::{Buffer, Change, Journal, Selected, Storage};
+use std::collections::HashMap;
+
+/// Amount of previous buffers kept, when exceeded the oldest is dropped.
+pub const HISTORY_LIMIT: usize = 64;
 
 /// A conveniance type for combining [Buffer], [Storage] and [Selected], since they are often used
 /// in combinayion with each other.
@@ -10,6 +14,12 @@ pub struct BufferStorage<T> {
     pub buffer: Buffer,
     /// An optional single index.
     pub selected: Selected,
+    /// Previous buffers, most recent last.
+    history: Vec<Buffer>,
+    /// Buffers saved under a name.
+    named: HashMap<String, Buffer>,
+    /// Changes that may be undone or redone.
+    journal: Journal<T>,
 }
 
 /// Error type for when getting an item based on [Selected] fails.
@@ -41,23 +51,259 @@ impl<T> BufferStorage<T> {
             storage,
             buffer,
             selected,
+            history: Vec::new(),
+            named: HashMap::new(),
+            journal: Journal::default(),
         }
     }
 
-    /// Reset the [Selected] and [Buffer] of the [`BufferStorage`].
-    /// Important to note that the [Storage] stays the same.
+    /// Reset the [Selected] and [Buffer] of the [`BufferStorage`], the previous [Buffer] is kept
+    /// in history. Important to note that the [Storage] stays the same.
     pub fn reset(&mut self) -> &Self {
+        self.push_history();
         self.buffer.reset();
         self.selected.clear();
         self
     }
 
-    /// Filter the [Buffer] in place based on the condition applied to the contents of the [Storage].
+    /// Filter the [Buffer] in place based on the condition applied to the contents of the [Storage],
+    /// the previous [Buffer] is kept in history.
     pub fn filter_in_place(&mut self, f: impl FnMut(&T) -> bool) -> &mut Self {
+        self.push_history();
         self.buffer.filter_in_place(&self.storage, f);
         self
     }
 
+    /// Filter the [Buffer] in place based on the condition applied to the indices of the
+    /// [Storage], the previous [Buffer] is kept in history.
+    pub fn filter_indices_in_place(&mut self, f: impl FnMut(&usize) -> bool) -> &mut Self {
+        self.push_history();
+        self.buffer.filter_indices_in_place(self.storage.len(), f);
+        self
+    }
+
+    /// Keep a copy of the current [Buffer] in history, such that it may be returned to using
+    /// [`BufferStorage::pop_history`].
+    pub fn push_history(&mut self) -> &mut Self {
+        if self.history.len() >= HISTORY_LIMIT {
+            self.history.remove(0);
+        }
+        self.history.push(self.buffer.clone());
+        self
+    }
+
+    /// Return to the most recent [Buffer] in history, returns whether or not there was one.
+    pub fn pop_history(&mut self) -> bool {
+        if let Some(buffer) = self.history.pop() {
+            self.buffer = buffer;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Amount of buffers in history.
+    #[must_use]
+    pub fn history_len(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Save the current [Buffer] under a name, replacing any buffer previously saved under it.
+    pub fn name_buffer(&mut self, name: impl Into<String>) -> &mut Self {
+        self.named.insert(name.into(), self.buffer.clone());
+        self
+    }
+
+    /// Replace the current [Buffer] by one saved under a name, the current [Buffer] is kept in
+    /// history, returns whether or not a buffer was saved under the name.
+    pub fn recall_buffer(&mut self, name: &str) -> bool {
+        let Some(buffer) = self.named.get(name).cloned() else {
+            return false;
+        };
+        self.replace_buffer(buffer);
+        true
+    }
+
+    /// Get a [Buffer] saved under a name.
+    #[must_use]
+    pub fn named_buffer(&self, name: &str) -> Option<&Buffer> {
+        self.named.get(name)
+    }
+
+    /// Replace the current [Buffer], the current [Buffer] is kept in history.
+    pub fn replace_buffer(&mut self, buffer: Buffer) -> &mut Self {
+        self.push_history();
+        self.buffer = buffer;
+        self
+    }
+
+    /// Names buffers have been saved under, in no particular order.
+    pub fn buffer_names(&self) -> impl Iterator<Item = &str> {
+        self.named.keys().map(String::as_str)
+    }
+
+    /// Remove the items at the given indices from the [Storage], returning them in the order they
+    /// were stored. The [Buffer], [Selected], history and named buffers are updated such that
+    /// their indices refer to the same items as before, dropping the removed ones.
+    pub fn remove(&mut self, indices: impl IntoIterator<Item = usize>) -> Vec<T> {
+        self.remove_indexed(indices)
+            .into_iter()
+            .map(|(_, item)| item)
+            .collect()
+    }
+
+    /// Remove the items at the given indices like [`BufferStorage::remove`], returning them along
+    /// with the index each was stored at.
+    pub fn remove_indexed(&mut self, indices: impl IntoIterator<Item = usize>) -> Vec<(usize, T)> {
+        let mut indices = indices
+            .into_iter()
+            .filter(|index| *index < self.storage.len())
+            .collect::<Vec<_>>();
+        indices.sort_unstable();
+        indices.dedup();
+
+        let map = |index: usize| match indices.binary_search(&index) {
+            Ok(_) => None,
+            Err(removed_before) => Some(index - removed_before),
+        };
+
+        self.buffer.remap(map);
+        self.selected.remap(map);
+        for buffer in self.history.iter_mut().chain(self.named.values_mut()) {
+            buffer.remap(map);
+        }
+
+        let removed = self.storage.remove(&indices);
+        indices.into_iter().zip(removed).collect()
+    }
+
+    /// Insert items such that each ends up at its given index, the items are expected to be
+    /// ordered by index. The [Buffer], [Selected], history and named buffers are updated such that
+    /// their indices refer to the same items as before.
+    pub fn insert(&mut self, items: Vec<(usize, T)>) -> &mut Self {
+        let len = self.storage.len() + items.len();
+        let mut inserted = items.iter().map(|(index, _)| *index).peekable();
+        let shifted = (0..len)
+            .filter(|index| inserted.next_if_eq(index).is_none())
+            .collect::<Vec<_>>();
+
+        let map = |index: usize| shifted.get(index).copied();
+
+        self.buffer.remap(map);
+        self.selected.remap(map);
+        for buffer in self.history.iter_mut().chain(self.named.values_mut()) {
+            buffer.remap(map);
+        }
+
+        self.storage.insert(items);
+        self
+    }
+
+    /// Reorder the [Storage] such that the item at each index is the one previously at the index
+    /// given at that position in `order`, which should hold every index once. The [Buffer],
+    /// [Selected], history and named buffers are updated such that their indices refer to the same
+    /// items as before. Returns the order undoing the permutation.
+    pub fn permute(&mut self, order: &[usize]) -> Vec<usize> {
+        let mut inverse = vec![0; order.len()];
+        for (index, previous) in order.iter().enumerate() {
+            inverse[*previous] = index;
+        }
+
+        let map = |index: usize| inverse.get(index).copied();
+        self.buffer.remap(map);
+        self.selected.remap(map);
+        for buffer in self.history.iter_mut().chain(self.named.values_mut()) {
+            buffer.remap(map);
+        }
+
+        // follow each cycle of the permutation, swapping items into place
+        let mut placed = vec![false; order.len()];
+        for start in 0..order.len() {
+            let mut current = start;
+            while !placed[current] {
+                placed[current] = true;
+                let next = order[current];
+                if next == start {
+                    break;
+                }
+                self.storage.swap(current, next);
+                current = next;
+            }
+        }
+
+        inverse
+    }
+
+    /// Record a change undoing an operation that has been performed on the [Storage].
+    pub fn record(&mut self, description: impl Into<String>, change: Change<T>) -> &mut Self {
+        self.journal.record(description, change);
+        self
+    }
+
+    /// Undo the most recently recorded change, giving its description if there was one.
+    pub fn undo(&mut self) -> Option<String> {
+        let (description, change) = self.journal.take_undo()?;
+        let inverse = self.apply(change);
+        self.journal.push_redo(description.clone(), inverse);
+        Some(description)
+    }
+
+    /// Redo the most recently undone change, giving its description if there was one.
+    pub fn redo(&mut self) -> Option<String> {
+        let (description, change) = self.journal.take_redo()?;
+        let inverse = self.apply(change);
+        self.journal.push_redone(description.clone(), inverse);
+        Some(description)
+    }
+
+    /// Get the [Journal] of changes that may be undone or redone.
+    #[must_use]
+    pub fn journal(&self) -> &Journal<T> {
+        &self.journal
+    }
+
+    /// Get the [Journal] of changes that may be undone or redone as mutable.
+    pub fn journal_mut(&mut self) -> &mut Journal<T> {
+        &mut self.journal
+    }
+
+    /// Apply a change to the [Storage], returning the change undoing it.
+    pub(super) fn apply(&mut self, change: Change<T>) -> Change<T> {
+        match change {
+            Change::Replace(items) => Change::Replace(
+                items
+                    .into_iter()
+                    .filter_map(|(index, item)| {
+                        let current = self.storage.get_mut(index)?;
+                        Some((index, std::mem::replace(current, item)))
+                    })
+                    .collect(),
+            ),
+            Change::Insert(items) => {
+                let indices = items.iter().map(|(index, _)| *index).collect();
+                self.insert(items);
+                Change::Remove(indices)
+            }
+            Change::Remove(indices) => Change::Insert(self.remove_indexed(indices)),
+            Change::Truncate(len) => Change::Extend(self.remove(len..self.storage.len())),
+            Change::Extend(items) => {
+                let len = self.storage.len();
+                self.storage.extend(items);
+                Change::Truncate(len)
+            }
+            Change::Permute(order) => Change::Permute(self.permute(&order)),
+            Change::Linked(change) => Change::Linked(change.apply()),
+            Change::Many(changes) => {
+                let mut inverse = changes
+                    .into_iter()
+                    .map(|change| self.apply(change))
+                    .collect::<Vec<_>>();
+                inverse.reverse();
+                Change::Many(inverse)
+            }
+        }
+    }
+
     /// Get the currently selected item in the [Storage] based on the [Selected].
     ///
     /// # Errors
@@ -120,3 +366,36 @@ impl<T> BufferStorage<T> {
             .map_while(|i| Some((i, self.storage.get(i)?)))
     }
 }
+
+/// Items that may be referred to by an id as well as by their index.
+pub trait Identify {
+    /// Id a reference is parsed as before being compared to items.
+    type Id;
+
+    /// Parse a reference as an id, [None] if no item could have it.
+    fn parse_id(reference: &str) -> Option<Self::Id>;
+
+    /// Check whether the item has the given id.
+    fn has_id(&self, id: &Self::Id) -> bool;
+}
+
+impl<T> BufferStorage<T>
+where
+    T: Identify,
+{
+    /// Find the index of an item in the [Storage] by either its index or its id.
+    /// References that are both a valid index and an id resolve to the index.
+    #[must_use]
+    pub fn index_of(&self, reference: &str) -> Option<usize> {
+        if let Some(index) = reference
+            .parse::<usize>()
+            .ok()
+            .filter(|index| *index < self.storage.len())
+        {
+            return Some(index);
+        }
+
+        let id = T::parse_id(reference)?;
+        self.storage.iter().position(|item| item.has_id(&id))
+    }
+}
@@ -0,0 +1,166 @@
+use crate::shared;
+use std::{collections::VecDeque, fmt::Debug};
+
+/// Default amount of changes kept by a [Journal].
+pub const JOURNAL_LIMIT: usize = 64;
+
+/// A change to a [`Storage`](super::Storage), used to record the inverse of operations such that they may be undone.
+#[derive(Debug, Clone)]
+pub enum Change<T> {
+    /// Replace the items at the given indices.
+    Replace(Vec<(usize, T)>),
+    /// Insert items such that each ends up at its index, ordered by index.
+    Insert(Vec<(usize, T)>),
+    /// Remove the items at the given indices.
+    Remove(Vec<usize>),
+    /// Remove every item from the given index and onwards.
+    Truncate(usize),
+    /// Add items to the end.
+    Extend(Vec<T>),
+    /// Reorder items such that the item at each index is the one previously at the index given
+    /// at that position.
+    Permute(Vec<usize>),
+    /// Apply several changes in order.
+    Many(Vec<Change<T>>),
+    /// Apply a change to another storage.
+    Linked(Box<dyn Linked>),
+}
+
+/// Change to another storage made by the same operation as a [Change] holding it, such that both
+/// are undone and redone together.
+pub trait Linked: Debug + Send + Sync {
+    /// Apply the change, returning the change undoing it.
+    fn apply(self: Box<Self>) -> Box<dyn Linked>;
+
+    /// Clone the change into a new box.
+    fn clone_box(&self) -> Box<dyn Linked>;
+}
+
+impl Clone for Box<dyn Linked> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// A [Change] to a shared [`BufferStorage`](super::BufferStorage), usable as a [Linked] change.
+#[derive(Debug, Clone)]
+pub struct SharedChange<T> {
+    /// Storage the change is applied to.
+    pub buffer_storage: shared::BufferStorage<T>,
+    /// Change to apply.
+    pub change: Change<T>,
+}
+
+impl<T> Linked for SharedChange<T>
+where
+    T: Debug + Clone + Send + Sync + 'static,
+{
+    fn apply(self: Box<Self>) -> Box<dyn Linked> {
+        let change = self.buffer_storage.write().apply(self.change);
+        Box::new(Self {
+            buffer_storage: self.buffer_storage,
+            change,
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn Linked> {
+        Box::new(self.clone())
+    }
+}
+
+/// Bounded record of changes that may be undone and redone, with a description of what caused each
+/// change.
+#[derive(Debug, Clone)]
+pub struct Journal<T> {
+    undo: VecDeque<(String, Change<T>)>,
+    redo: Vec<(String, Change<T>)>,
+    limit: usize,
+}
+
+impl<T> Default for Journal<T> {
+    fn default() -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            limit: JOURNAL_LIMIT,
+        }
+    }
+}
+
+impl<T> Journal<T> {
+    /// Record a change undoing an operation, anything that could be redone is forgotten.
+    pub fn record(&mut self, description: impl Into<String>, change: Change<T>) -> &mut Self {
+        self.redo.clear();
+        self.push_undo(description.into(), change);
+        self
+    }
+
+    fn push_undo(&mut self, description: String, change: Change<T>) {
+        if self.limit == 0 {
+            return;
+        }
+        while self.undo.len() >= self.limit {
+            self.undo.pop_front();
+        }
+        self.undo.push_back((description, change));
+    }
+
+    /// Take the most recent change that may be undone.
+    pub fn take_undo(&mut self) -> Option<(String, Change<T>)> {
+        self.undo.pop_back()
+    }
+
+    /// Take the most recent change that may be redone.
+    pub fn take_redo(&mut self) -> Option<(String, Change<T>)> {
+        self.redo.pop()
+    }
+
+    /// Keep the inverse of an undone change, such that it may be redone.
+    pub fn push_redo(&mut self, description: String, change: Change<T>) -> &mut Self {
+        self.redo.push((description, change));
+        self
+    }
+
+    /// Keep the inverse of a redone change, such that it may be undone again, unlike
+    /// [`Journal::record`] this keeps what may be redone.
+    pub fn push_redone(&mut self, description: String, change: Change<T>) -> &mut Self {
+        self.push_undo(description, change);
+        self
+    }
+
+    /// Amount of changes that may be undone.
+    #[must_use]
+    pub fn undo_len(&self) -> usize {
+        self.undo.len()
+    }
+
+    /// Amount of changes that may be redone.
+    #[must_use]
+    pub fn redo_len(&self) -> usize {
+        self.redo.len()
+    }
+
+    /// Maximum amount of changes that may be undone.
+    #[must_use]
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Set the maximum amount of changes that may be undone, the oldest changes are forgotten if
+    /// more are recorded.
+    pub fn set_limit(&mut self, limit: usize) -> &mut Self {
+        self.limit = limit;
+        while self.undo.len() > limit {
+            self.undo.pop_front();
+        }
+        self.redo.drain(..self.redo.len().saturating_sub(limit));
+        self
+    }
+
+    /// Forget every recorded change.
+    pub fn clear(&mut self) -> &mut Self {
+        self.undo.clear();
+        self.redo.clear();
+        self
+    }
+}
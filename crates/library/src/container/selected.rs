@@ -28,4 +28,11 @@ impl Selected {
         self.index.replace(value);
         self
     }
+
+    /// Update the selected index using a map from old to new indices, if the map gives none
+    /// nothing will be selected.
+    pub fn remap(&mut self, map: impl FnOnce(usize) -> Option<usize>) -> &mut Self {
+        self.index = self.index.and_then(map);
+        self
+    }
 }
@@ -13,7 +13,42 @@ impl<T> Storage<T> {
     }
     /// Extend the storage with an [`IntoIterator`] of values.
     pub fn extend(&mut self, iter: impl IntoIterator<Item = T>) -> &mut Self {
-        self.content.extend(iter.into_iter());
+        self.content.extend(iter);
+        self
+    }
+
+    /// Remove the values at the given indices, indices out of bounds are ignored. The removed
+    /// values are returned in the order they were stored. Any indices into the storage need to be
+    /// updated, see [`BufferStorage::remove`](super::BufferStorage::remove).
+    pub fn remove(&mut self, indices: &[usize]) -> Vec<T> {
+        let mut removed = vec![false; self.content.len()];
+        for index in indices {
+            if let Some(removed) = removed.get_mut(*index) {
+                *removed = true;
+            }
+        }
+
+        let (removed, kept) = std::mem::take(&mut self.content)
+            .into_iter()
+            .zip(removed)
+            .partition::<Vec<_>, _>(|(_, removed)| *removed);
+        self.content = kept.into_iter().map(|(value, _)| value).collect();
+
+        removed.into_iter().map(|(value, _)| value).collect()
+    }
+
+    /// Insert values such that each ends up at its given index, the values are expected to be
+    /// ordered by index. Any indices into the storage need to be updated, see
+    /// [`BufferStorage::insert`](super::BufferStorage::insert).
+    pub fn insert(&mut self, values: Vec<(usize, T)>) -> &mut Self {
+        let mut content = Vec::with_capacity(self.content.len() + values.len());
+        let mut current = std::mem::take(&mut self.content).into_iter();
+        for (index, value) in values {
+            content.extend(current.by_ref().take(index.saturating_sub(content.len())));
+            content.push(value);
+        }
+        content.extend(current);
+        self.content = content;
         self
     }
 
@@ -23,6 +58,12 @@ impl<T> Storage<T> {
     }
 }
 
+impl<T> From<Vec<T>> for Storage<T> {
+    fn from(content: Vec<T>) -> Self {
+        Self { content }
+    }
+}
+
 impl<T> AsRef<Vec<T>> for Storage<T> {
     fn as_ref(&self) -> &Vec<T> {
         &self.content
@@ -0,0 +1,65 @@
+//! Versioning of the file format used to save bookmarks, categories and info.
+
+use crate::{bookmark::Bookmark, category::Category, info::Info};
+use bookmark_storage::Migrations;
+use std::sync::LazyLock;
+
+/// Current version of the file format, written as a header when saving.
+///
+/// Version 2 added timestamps to bookmarks and version 3 added identifiers to bookmarks, neither
+/// need a migration since the fields may be absent.
+pub const VERSION: u32 = 3;
+
+/// Migrations upgrading lines of files written with older format versions.
+pub static MIGRATIONS: LazyLock<Migrations> = LazyLock::new(|| {
+    Migrations::new(VERSION)
+        .register::<Info>(0, escape_legacy)
+        .register::<Category>(0, escape_legacy)
+        .register::<Bookmark>(0, escape_legacy)
+});
+
+/// Version 0 files did not escape field content, any escape character is literal.
+fn escape_legacy(line: String) -> String {
+    if line.contains(bookmark_storage::token::ESCAPE) {
+        line.replace('\\', "\\\\")
+    } else {
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::token;
+    use bookmark_storage::Storeable;
+    use std::io::BufRead;
+
+    fn load(content: &str) -> Vec<Bookmark> {
+        let lines = MIGRATIONS
+            .upgrade(content.as_bytes().lines().enumerate())
+            .expect("header should be valid");
+        bookmark_storage::load_from(lines).expect("lines should parse")
+    }
+
+    #[test]
+    pub fn legacy_escape() {
+        let legacy = format!(
+            "{}\n<url> a\\b <info> c:\\dir <tag> \\d+\n{}\n",
+            token::unsorted::BEGIN,
+            token::unsorted::END,
+        );
+
+        let bookmarks = load(&legacy);
+        assert_eq!(bookmarks[0].url(), "a\\b");
+        assert_eq!(bookmarks[0].description(), "c:\\dir");
+        assert_eq!(bookmarks[0].tags().collect::<Vec<_>>(), ["\\d+"]);
+
+        let mut saved = Vec::new();
+        bookmark_storage::write_header(&mut saved, VERSION).expect("write to vec should succeed");
+        bookmark_storage::save(&mut saved, bookmarks.iter()).expect("write to vec should succeed");
+        let saved = String::from_utf8(saved).expect("saved content should be utf-8");
+
+        let reloaded = load(&saved);
+        assert_eq!(reloaded[0].to_line(), bookmarks[0].to_line());
+    }
+}
@@ -3,7 +3,7 @@ use bookmark_storage::{ContentString, ListField, Section, Storeable};
 
 /// Used for storing info relevant for all bookmarks and categories, such as top level categories
 /// and available tags.
-#[derive(Debug, Storeable, Default)]
+#[derive(Debug, Storeable, Default, Clone)]
 pub struct Info {
     #[line]
     line: ContentString,
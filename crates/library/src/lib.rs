@@ -13,6 +13,8 @@
 
 pub mod command_map;
 pub mod container;
+pub mod format;
+pub mod query;
 pub mod token;
 
 /// More easily use shared [`container::BufferStorage`].
@@ -27,16 +29,23 @@ pub mod shared {
 
 use std::collections::HashMap;
 
-pub use bookmark::Bookmark;
-pub use category::{Category, IdentifierContainer, IdentifierErr};
+pub use bookmark::{Bookmark, ReassignedId};
+pub use category::{Category, CategoryMatcher, IdentifierContainer, IdentifierErr};
+pub use category_check::{check_categories, CategoryIssue};
 pub use command_factory::CommandFactory;
 pub use info::Info;
+pub use membership::{category_membership, Membership};
+pub use timestamp::{Timestamp, TimestampErr};
 
 mod bookmark;
 mod category;
+mod category_check;
 mod command_factory;
 mod info;
+mod membership;
 mod parse_command;
+mod timestamp;
+mod url;
 
 use thiserror::Error;
 
@@ -60,20 +69,29 @@ pub struct Graph {
 }
 
 impl Graph {
+    /// Create a graph with the given amount of nodes and no edges.
+    #[must_use]
+    pub fn new(node_count: usize) -> Self {
+        Self {
+            data: vec![false; node_count * node_count].into_boxed_slice(),
+            node_count,
+        }
+    }
+
     /// Check if there is an edge between give nodes.
     ///
     /// # Errors
     /// If a node index is invalid.
     pub fn is_edge(&self, from: usize, to: usize) -> Result<bool, GraphError> {
-        if from > self.node_count {
+        if from >= self.node_count {
             Err(GraphError::InvalidNode {
                 value: from,
-                max: self.node_count,
+                max: self.node_count.saturating_sub(1),
             })
-        } else if to > self.node_count {
+        } else if to >= self.node_count {
             Err(GraphError::InvalidNode {
                 value: to,
-                max: self.node_count,
+                max: self.node_count.saturating_sub(1),
             })
         } else {
             Ok(self.data[from * self.node_count + to])
@@ -85,44 +103,83 @@ impl Graph {
     /// # Errors
     /// If to or from is not a node in graph.
     pub fn set_edge(&mut self, from: usize, to: usize, value: bool) -> Result<(), GraphError> {
-        if from > self.node_count {
+        if from >= self.node_count {
             Err(GraphError::InvalidNode {
                 value: from,
-                max: self.node_count,
+                max: self.node_count.saturating_sub(1),
             })
-        } else if to > self.node_count {
+        } else if to >= self.node_count {
             Err(GraphError::InvalidNode {
                 value: to,
-                max: self.node_count,
+                max: self.node_count.saturating_sub(1),
             })
         } else {
             self.data[from * self.node_count + to] = value;
             Ok(())
         }
     }
+
+    /// Amount of nodes in graph.
+    #[must_use]
+    pub fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    /// Iterate the nodes with an edge from the given node, in order. Invalid nodes have no edges.
+    pub fn edges(&self, from: usize) -> impl Iterator<Item = usize> + '_ {
+        let row = if from < self.node_count {
+            &self.data[from * self.node_count..(from + 1) * self.node_count]
+        } else {
+            &[]
+        };
+        row.iter()
+            .enumerate()
+            .filter(|(_, edge)| **edge)
+            .map(|(to, _)| to)
+    }
+
+    /// Get which nodes can be reached by following edges from any of the given nodes, the given
+    /// nodes are themselves reached. Invalid nodes are ignored.
+    #[must_use]
+    pub fn reachable(&self, from: impl IntoIterator<Item = usize>) -> Vec<bool> {
+        let mut reached = vec![false; self.node_count];
+        let mut stack = from
+            .into_iter()
+            .filter(|node| *node < self.node_count)
+            .collect::<Vec<_>>();
+
+        while let Some(node) = stack.pop() {
+            if std::mem::replace(&mut reached[node], true) {
+                continue;
+            }
+
+            stack.extend(self.edges(node).filter(|to| !reached[*to]));
+        }
+
+        reached
+    }
 }
 
 impl From<&[Category]> for Graph {
     fn from(value: &[Category]) -> Self {
-        let mut graph = Self {
-            data: vec![false; value.len() * value.len()].into_boxed_slice(),
-            node_count: value.len(),
-        };
+        let mut graph = Self::new(value.len());
 
-        let mut cat_map = HashMap::new();
+        let mut cat_map = HashMap::<_, Vec<_>>::new();
         for (i, category) in value.iter().enumerate() {
-            cat_map.insert(Box::new(category.id()), i);
+            cat_map.entry(category.id()).or_default().push(i);
         }
 
         for (i, category) in value.iter().enumerate() {
             for child in category.subcategories() {
-                let Some(sub_i) = cat_map.get(&Box::new(child)) else {
+                let Some(sub_indices) = cat_map.get(child) else {
                     continue;
                 };
 
-                if let Err(err) = graph.set_edge(i, *sub_i, true) {
-                    eprintln!("{err}");
-                };
+                for sub_i in sub_indices {
+                    if let Err(err) = graph.set_edge(i, *sub_i, true) {
+                        eprintln!("{err}");
+                    }
+                }
             }
         }
 
@@ -161,7 +218,7 @@ pub fn run(
     let eval_command = |command: &str, fatal_errors| -> Result<(), i32> {
         let split_command = parse_command::parse_command(command.trim());
 
-        let [command, args @ .. ] = &split_command[..] else {
+        let [command, args @ ..] = &split_command[..] else {
             println!("could not parse \"{command}\"");
             return Ok(());
         };
@@ -176,7 +233,7 @@ pub fn run(
                     println!("incorrect usage: {msg}");
                     if let Some(help) = command_map.help(command) {
                         println!("{help}");
-                    };
+                    }
                 }
                 err => println!("{err}"),
             }
@@ -196,7 +253,7 @@ pub fn run(
                     println!("error running init commands");
                     return code;
                 }
-                Ok(_) => (),
+                Ok(()) => (),
             }
         }
     }
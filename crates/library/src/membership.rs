@@ -0,0 +1,135 @@
+use crate::{bookmark::Bookmark, category::Category, IdentifierErr};
+use std::collections::HashMap;
+
+/// Which categories match each bookmark, as given by [`category_membership`].
+#[derive(Debug, Clone, Default)]
+pub struct Membership {
+    members: Vec<Vec<usize>>,
+    category_count: usize,
+}
+
+impl Membership {
+    /// Indices of the categories matching the bookmark at the given index, empty if the index is
+    /// out of range.
+    #[must_use]
+    pub fn categories_of(&self, bookmark: usize) -> &[usize] {
+        self.members.get(bookmark).map_or(&[], Vec::as_slice)
+    }
+
+    /// Amount of bookmarks the membership was evaluated for.
+    #[must_use]
+    pub fn bookmark_count(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Indices of bookmarks not matched by any category.
+    pub fn uncategorized(&self) -> impl Iterator<Item = usize> + '_ {
+        self.members
+            .iter()
+            .enumerate()
+            .filter_map(|(i, categories)| categories.is_empty().then_some(i))
+    }
+
+    /// Amount of bookmarks matched by each category, indexed the same as the categories.
+    #[must_use]
+    pub fn match_counts(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.category_count];
+        for category in self.members.iter().flatten() {
+            counts[*category] += 1;
+        }
+        counts
+    }
+
+    /// Amount of bookmarks matched by both categories of a pair, for every pair sharing at least
+    /// one bookmark. The first index of a pair is always the lower, pairs with the most shared
+    /// bookmarks come first.
+    #[must_use]
+    pub fn overlap_counts(&self) -> Vec<((usize, usize), usize)> {
+        let mut counts = HashMap::new();
+        for categories in &self.members {
+            for (i, first) in categories.iter().enumerate() {
+                for second in &categories[i + 1..] {
+                    *counts.entry((*first, *second)).or_insert(0) += 1;
+                }
+            }
+        }
+
+        let mut counts = counts.into_iter().collect::<Vec<_>>();
+        counts.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+        counts
+    }
+}
+
+/// Evaluate every category against every bookmark.
+///
+/// # Errors
+/// If the identifiers of any category are malformed.
+pub fn category_membership(
+    categories: &[Category],
+    bookmarks: &[Bookmark],
+) -> Result<Membership, IdentifierErr> {
+    let matchers = categories
+        .iter()
+        .map(Category::matcher)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let members = bookmarks
+        .iter()
+        .map(|bookmark| {
+            matchers
+                .iter()
+                .enumerate()
+                .filter_map(|(i, matcher)| matcher.is_match(bookmark).then_some(i))
+                .collect()
+        })
+        .collect();
+
+    Ok(Membership {
+        members,
+        category_count: categories.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn membership_counts() {
+        let category = |id: &str, identifier: &str| {
+            Category::new(
+                id,
+                id,
+                "",
+                [identifier].into_iter(),
+                std::iter::empty::<&str>(),
+            )
+        };
+        let categories = [
+            category("rust", "#rust"),
+            category("docs", "@docs.rs"),
+            category("async", "~async"),
+        ];
+        let bookmarks = [
+            Bookmark::new("https://docs.rs/tokio", "async io", ["rust"].into_iter()),
+            Bookmark::new("https://go.dev", "", ["go"].into_iter()),
+            Bookmark::new("https://docs.rs/serde", "", std::iter::empty::<&str>()),
+            Bookmark::new("https://example.com", "", std::iter::empty::<&str>()),
+        ];
+
+        let membership =
+            category_membership(&categories, &bookmarks).expect("identifiers should be valid");
+
+        assert_eq!(membership.bookmark_count(), 4);
+        assert_eq!(membership.categories_of(0), [0, 1, 2]);
+        assert!(membership.categories_of(4).is_empty());
+        assert_eq!(membership.uncategorized().collect::<Vec<_>>(), [1, 3]);
+        assert_eq!(membership.match_counts(), [1, 2, 1]);
+        assert_eq!(
+            membership.overlap_counts(),
+            [((0, 1), 1), ((0, 2), 1), ((1, 2), 1)]
+        );
+
+        assert!(category_membership(&[category("bad", "/(")], &bookmarks).is_err());
+    }
+}
@@ -1,9 +1,10 @@
 use regex::Regex;
 
 pub fn parse_command(line: &str) -> Vec<String> {
-    use once_cell::sync::Lazy;
-    static ARG_RE: Lazy<Regex> =
-        Lazy::new(|| Regex::new(r#"\s*"(.*?)"\s*|$"#).expect("failure to compile ARG_RE regex"));
+    use std::sync::LazyLock;
+    static ARG_RE: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"\s*"(.*?)"\s*|$"#).expect("failure to compile ARG_RE regex")
+    });
 
     let mut next_start = 0;
     let mut arg_vec = Vec::new();
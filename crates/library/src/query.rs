@@ -0,0 +1,428 @@
+//! Query language used to filter any [Storeable] by its properties.
+//!
+//! A query consists of terms combined by `AND`, `OR`, `NOT` and parentheses, where `NOT` binds
+//! the tightest and `OR` the loosest, terms next to each other are combined by `AND`. A term is
+//! either a value matched against some default properties or `property:value` matched against a
+//! single property, values may be quoted to contain whitespace or parentheses. A word is only split
+//! at `:` if what precedes it is a property or alias, such that `https://example.com` is a value.
+//!
+//! Values are matched as case insensitive substrings, list properties match if any item does.
+//! `desc` and `tag` may be used for `description` and `tags`, `domain:value` matches urls whose
+//! host is value or a subdomain of it.
+//!
+//! `tag:rust AND (url:github OR desc:"async io") AND NOT domain:reddit.com`
+
+use crate::url;
+use bookmark_storage::Storeable;
+use std::iter::Peekable;
+use thiserror::Error;
+
+/// Error returned when a [Query] cannot be parsed.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum QueryErr {
+    /// If the query contains no terms.
+    #[error("query is empty")]
+    Empty,
+    /// If the query ends where a term or closing parenthesis was expected.
+    #[error("query ended unexpectedly")]
+    UnexpectedEnd,
+    /// If something was found where it does not belong.
+    #[error("unexpected {0} in query")]
+    Unexpected(String),
+    /// If a quoted value is never closed.
+    #[error("unclosed quote in query")]
+    UnclosedQuote,
+}
+
+/// Parsed query, see [module level documentation](self) for syntax.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Query {
+    /// Match a value against a property, or against default properties if none is given.
+    Term {
+        /// Property matched against.
+        property: Option<String>,
+        /// Lowercased value to match.
+        value: String,
+    },
+    /// Match if the inner query does not.
+    Not(Box<Query>),
+    /// Match if both queries do.
+    And(Box<Query>, Box<Query>),
+    /// Match if either query does.
+    Or(Box<Query>, Box<Query>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Open,
+    Close,
+    Word { text: String, quoted: bool },
+}
+
+impl Token {
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Token::Word { text, quoted: false } if text == keyword)
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Token::Open => "\"(\"".into(),
+            Token::Close => "\")\"".into(),
+            Token::Word { text, .. } => format!("\"{text}\""),
+        }
+    }
+}
+
+fn tokenize(query: &str) -> Result<Vec<Token>, QueryErr> {
+    let mut tokens = Vec::new();
+    let mut chars = query.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            '"' => {
+                chars.next();
+                let mut text = String::new();
+                loop {
+                    match chars.next().ok_or(QueryErr::UnclosedQuote)? {
+                        '"' => break,
+                        '\\' => text.push(chars.next().ok_or(QueryErr::UnclosedQuote)?),
+                        c => text.push(c),
+                    }
+                }
+                tokens.push(Token::Word { text, quoted: true });
+            }
+            _ => {
+                let mut text = String::new();
+                while let Some(c) =
+                    chars.next_if(|c| !c.is_whitespace() && !matches!(c, '(' | ')' | '"'))
+                {
+                    text.push(c);
+                }
+                tokens.push(Token::Word {
+                    text,
+                    quoted: false,
+                });
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Resolve what precedes `:` in a word to the property it scopes a term to.
+fn resolve_scope(scope: &str, properties: &[&str]) -> Option<String> {
+    match scope {
+        _ if properties.contains(&scope) => Some(scope.into()),
+        "domain" if properties.contains(&"url") => Some(scope.into()),
+        "desc" if properties.contains(&"description") => Some("description".into()),
+        "tag" if properties.contains(&"tags") => Some("tags".into()),
+        _ => None,
+    }
+}
+
+struct Parser<I: Iterator<Item = Token>> {
+    tokens: Peekable<I>,
+    properties: &'static [&'static str],
+}
+
+impl<I> Parser<I>
+where
+    I: Iterator<Item = Token>,
+{
+    fn or(&mut self) -> Result<Query, QueryErr> {
+        let mut query = self.and()?;
+        while self
+            .tokens
+            .next_if(|token| token.is_keyword("OR"))
+            .is_some()
+        {
+            query = Query::Or(Box::new(query), Box::new(self.and()?));
+        }
+        Ok(query)
+    }
+
+    fn and(&mut self) -> Result<Query, QueryErr> {
+        let mut query = self.not()?;
+        loop {
+            let explicit = self
+                .tokens
+                .next_if(|token| token.is_keyword("AND"))
+                .is_some();
+            let implicit = self
+                .tokens
+                .peek()
+                .is_some_and(|token| *token != Token::Close && !token.is_keyword("OR"));
+
+            if !explicit && !implicit {
+                return Ok(query);
+            }
+            query = Query::And(Box::new(query), Box::new(self.not()?));
+        }
+    }
+
+    fn not(&mut self) -> Result<Query, QueryErr> {
+        if self
+            .tokens
+            .next_if(|token| token.is_keyword("NOT"))
+            .is_some()
+        {
+            Ok(Query::Not(Box::new(self.not()?)))
+        } else {
+            self.primary()
+        }
+    }
+
+    fn primary(&mut self) -> Result<Query, QueryErr> {
+        match self.tokens.next().ok_or(QueryErr::UnexpectedEnd)? {
+            Token::Open => {
+                let query = self.or()?;
+                match self.tokens.next() {
+                    Some(Token::Close) => Ok(query),
+                    Some(token) => Err(QueryErr::Unexpected(token.describe())),
+                    None => Err(QueryErr::UnexpectedEnd),
+                }
+            }
+            token @ Token::Close => Err(QueryErr::Unexpected(token.describe())),
+            token @ Token::Word { .. } if ["AND", "OR"].iter().any(|k| token.is_keyword(k)) => {
+                Err(QueryErr::Unexpected(token.describe()))
+            }
+            Token::Word { text, quoted: true } => Ok(Query::Term {
+                property: None,
+                value: text.to_lowercase(),
+            }),
+            Token::Word {
+                text,
+                quoted: false,
+            } => {
+                let scoped = text.split_once(':').and_then(|(scope, value)| {
+                    Some((resolve_scope(scope, self.properties)?, value))
+                });
+                let Some((property, value)) = scoped else {
+                    return Ok(Query::Term {
+                        property: None,
+                        value: text.to_lowercase(),
+                    });
+                };
+
+                // allows values to be quoted, or to be a separate argument
+                let value = if value.is_empty() {
+                    match self.tokens.next() {
+                        Some(Token::Word { text, .. }) => text,
+                        Some(token) => return Err(QueryErr::Unexpected(token.describe())),
+                        None => return Err(QueryErr::UnexpectedEnd),
+                    }
+                } else {
+                    value.into()
+                };
+
+                Ok(Query::Term {
+                    property: Some(property),
+                    value: value.to_lowercase(),
+                })
+            }
+        }
+    }
+}
+
+impl Query {
+    /// Parse a query matching items of type `T`, terms may only be scoped to properties of `T`.
+    ///
+    /// # Errors
+    /// If the query is not valid.
+    pub fn parse<T: Storeable>(query: &str) -> Result<Self, QueryErr> {
+        let tokens = tokenize(query)?;
+        if tokens.is_empty() {
+            return Err(QueryErr::Empty);
+        }
+
+        let mut parser = Parser {
+            tokens: tokens.into_iter().peekable(),
+            properties: T::PROPERTIES,
+        };
+        let query = parser.or()?;
+
+        match parser.tokens.next() {
+            Some(token) => Err(QueryErr::Unexpected(token.describe())),
+            None => Ok(query),
+        }
+    }
+
+    /// Parse a query matching items of type `T` from command arguments, arguments containing
+    /// whitespace are treated as quoted.
+    ///
+    /// # Errors
+    /// If the arguments do not form a valid query.
+    pub fn from_args<T: Storeable>(args: &[impl AsRef<str>]) -> Result<Self, QueryErr> {
+        let query = args
+            .iter()
+            .map(|arg| {
+                let arg = arg.as_ref();
+                if arg.is_empty() || arg.contains(char::is_whitespace) {
+                    format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\""))
+                } else {
+                    arg.into()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        Self::parse::<T>(&query)
+    }
+
+    /// Properties are known to exist as they are resolved when parsing, a property missing
+    /// regardless, such as one of the defaults, does not match.
+    fn term_matches(item: &impl Storeable, property: &str, value: &str) -> bool {
+        match property {
+            "domain" => item.any_value("url", &mut |url| is_domain(url::domain(url), value)),
+            property => item.any_value(property, &mut |content| contains_lowercase(content, value)),
+        }
+        .unwrap_or(false)
+    }
+
+    /// Check whether or not an item matches the query, terms without a property are matched
+    /// against the given default properties.
+    #[must_use]
+    pub fn matches(&self, item: &impl Storeable, defaults: &[&str]) -> bool {
+        match self {
+            Query::Term {
+                property: Some(property),
+                value,
+            } => Self::term_matches(item, property, value),
+            Query::Term {
+                property: None,
+                value,
+            } => defaults
+                .iter()
+                .any(|property| Self::term_matches(item, property, value)),
+            Query::Not(query) => !query.matches(item, defaults),
+            Query::And(a, b) => a.matches(item, defaults) && b.matches(item, defaults),
+            Query::Or(a, b) => a.matches(item, defaults) || b.matches(item, defaults),
+        }
+    }
+}
+
+/// Check whether or not content contains an already lowercased value ignoring case, without
+/// allocating a lowercased copy of content.
+fn contains_lowercase(content: &str, value: &str) -> bool {
+    value.is_empty()
+        || content.char_indices().any(|(start, _)| {
+            let mut rest = content[start..].chars().flat_map(char::to_lowercase);
+            value.chars().all(|c| rest.next() == Some(c))
+        })
+}
+
+/// Check whether or not a domain is an already lowercased value or a subdomain of it.
+fn is_domain(domain: &str, value: &str) -> bool {
+    let Some(split) = domain.len().checked_sub(value.len()) else {
+        return false;
+    };
+    domain
+        .get(split..)
+        .is_some_and(|suffix| suffix.eq_ignore_ascii_case(value))
+        && (split == 0 || domain[..split].ends_with('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Bookmark;
+
+    fn term(property: Option<&str>, value: &str) -> Query {
+        Query::Term {
+            property: property.map(String::from),
+            value: value.into(),
+        }
+    }
+
+    fn parse(query: &str) -> Result<Query, QueryErr> {
+        Query::parse::<Bookmark>(query)
+    }
+
+    #[test]
+    pub fn parse_query() {
+        let query =
+            parse("tag:rust AND (url:github OR desc:\"Async IO\") AND NOT domain:reddit.com")
+                .expect("query should parse");
+        assert_eq!(
+            query,
+            Query::And(
+                Box::new(Query::And(
+                    Box::new(term(Some("tags"), "rust")),
+                    Box::new(Query::Or(
+                        Box::new(term(Some("url"), "github")),
+                        Box::new(term(Some("description"), "async io")),
+                    )),
+                )),
+                Box::new(Query::Not(Box::new(term(Some("domain"), "reddit.com")))),
+            )
+        );
+
+        assert_eq!(
+            parse("a b OR c"),
+            Ok(Query::Or(
+                Box::new(Query::And(
+                    Box::new(term(None, "a")),
+                    Box::new(term(None, "b"))
+                )),
+                Box::new(term(None, "c")),
+            ))
+        );
+        assert_eq!(
+            Query::from_args::<Bookmark>(&["desc:", "async io", "\"AND\""]),
+            parse("desc:\"async io\" AND \"AND\"")
+        );
+
+        assert_eq!(parse(""), Err(QueryErr::Empty));
+        assert_eq!(parse("(a"), Err(QueryErr::UnexpectedEnd));
+        assert_eq!(parse("a OR"), Err(QueryErr::UnexpectedEnd));
+        assert_eq!(parse("\"a"), Err(QueryErr::UnclosedQuote));
+        assert!(matches!(parse("a)"), Err(QueryErr::Unexpected(_))));
+        assert!(matches!(parse("AND a"), Err(QueryErr::Unexpected(_))));
+    }
+
+    #[test]
+    pub fn unscoped_colon() {
+        assert_eq!(
+            parse("https://github.com"),
+            Ok(term(None, "https://github.com"))
+        );
+        assert_eq!(
+            parse("url:https://GitHub.com"),
+            Ok(term(Some("url"), "https://github.com"))
+        );
+        assert_eq!(parse("size:1"), Ok(term(None, "size:1")));
+    }
+
+    #[test]
+    pub fn evaluate() {
+        let bookmark = Bookmark::new(
+            "https://old.Reddit.com/r/rust",
+            "Async IO in Rust",
+            ["rust", "reading"].into_iter(),
+        );
+        let matches = |query: &str| {
+            parse(query)
+                .expect("query should parse")
+                .matches(&bookmark, &["url", "description"])
+        };
+
+        assert!(matches("tag:rust desc:\"async io\""));
+        assert!(matches("domain:reddit.com"));
+        assert!(!matches("domain:dit.com"));
+        assert!(!matches("tag:rust AND NOT domain:reddit.com"));
+        assert!(matches("github OR r/rust"));
+        assert!(matches("tags:read"));
+        assert!(matches("https://old.reddit.com/r/"));
+        assert!(!matches("https://github.com"));
+    }
+}
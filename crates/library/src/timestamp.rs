@@ -0,0 +1,210 @@
+use std::{
+    fmt::Display,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use thiserror::Error;
+
+const SECS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Latest year a date may be parsed in, such that it is always written with four digits.
+const MAX_YEAR: i64 = 9999;
+
+/// Latest time a timestamp may hold, 9999-12-31T23:59:59Z, such that it is displayed with a year
+/// no later than [`MAX_YEAR`].
+const MAX_SECS: u64 = 253_402_300_799;
+
+/// Error returned when a [Timestamp] cannot be parsed.
+#[derive(Debug, Error, Clone)]
+#[error(
+    "\"{0}\" is not a timestamp, expected YYYY-MM-DD, YYYY-MM-DDTHH:MM:SSZ or seconds since 1970"
+)]
+pub struct TimestampErr(String);
+
+/// Point in time stored as seconds since 1970-01-01 UTC.
+///
+/// Displayed as `YYYY-MM-DDTHH:MM:SSZ`, parsed from that, from a plain `YYYY-MM-DD` date or from
+/// an amount of seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Timestamp(u64);
+
+impl Timestamp {
+    /// Create a timestamp from seconds since 1970-01-01 UTC, [None] if the time is after the
+    /// end of year 9999.
+    #[must_use]
+    pub fn from_secs(secs: u64) -> Option<Self> {
+        (secs <= MAX_SECS).then_some(Self(secs))
+    }
+
+    /// Get the current time, times before 1970 are clamped.
+    #[must_use]
+    pub fn now() -> Self {
+        Self(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_secs()),
+        )
+    }
+
+    /// Seconds since 1970-01-01 UTC.
+    #[must_use]
+    pub fn secs(self) -> u64 {
+        self.0
+    }
+
+    fn parse_date(date: &str) -> Option<i64> {
+        let mut parts = date.splitn(3, '-');
+        let year = parts.next()?.parse().ok()?;
+        let month = parts.next()?.parse().ok()?;
+        let day = parts.next()?.parse().ok()?;
+
+        if !(0..=MAX_YEAR).contains(&year)
+            || !(1..=12).contains(&month)
+            || !(1..=days_in_month(year, month)).contains(&day)
+        {
+            return None;
+        }
+        days_from_civil(year, month, day).checked_mul(SECS_PER_DAY)
+    }
+
+    fn parse_time(time: &str) -> Option<i64> {
+        let time = time.strip_suffix('Z').unwrap_or(time);
+        let mut parts = time.splitn(3, ':');
+        let hour: i64 = parts.next()?.parse().ok()?;
+        let minute: i64 = parts.next()?.parse().ok()?;
+        let second: i64 = parts.next().map_or(Some(0), |s| s.parse().ok())?;
+
+        (hour < 24 && minute < 60 && second < 60).then_some(hour * 3600 + minute * 60 + second)
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = TimestampErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let err = || TimestampErr(s.into());
+
+        if let Ok(secs) = s.parse() {
+            return Self::from_secs(secs).ok_or_else(err);
+        }
+
+        let secs = match s.split_once(['T', ' ']) {
+            Some((date, time)) => Self::parse_date(date)
+                .zip(Self::parse_time(time))
+                .and_then(|(date, time)| date.checked_add(time)),
+            None => Self::parse_date(s),
+        }
+        .ok_or_else(err)?;
+
+        u64::try_from(secs)
+            .ok()
+            .and_then(Self::from_secs)
+            .ok_or_else(err)
+    }
+}
+
+impl Display for Timestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let secs = i64::try_from(self.0).unwrap_or(i64::MAX);
+        let (year, month, day) = civil_from_days(secs.div_euclid(SECS_PER_DAY));
+        let time = secs.rem_euclid(SECS_PER_DAY);
+
+        write!(
+            f,
+            "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+            time / 3600,
+            time / 60 % 60,
+            time % 60
+        )
+    }
+}
+
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 of a date in the proleptic gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Date in the proleptic gregorian calendar of days since 1970-01-01, reverses
+/// [`days_from_civil`].
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn parse_and_display() {
+        let parse = |s: &str| s.parse::<Timestamp>().map(Timestamp::secs).ok();
+
+        assert_eq!(parse("1970-01-01"), Some(0));
+        assert_eq!(parse("86400"), Some(86400));
+        assert_eq!(parse("2000-02-29"), Some(951_782_400));
+        assert_eq!(parse("2026-10-18T12:30:05Z"), Some(1_792_326_605));
+        assert_eq!(parse("2026-10-18 12:30"), Some(1_792_326_600));
+        assert_eq!(parse("2023-02-29"), None);
+        assert_eq!(parse("1969-12-31"), None);
+        assert_eq!(parse("yesterday"), None);
+        assert_eq!(parse("9999999999999999-01-01"), None);
+        assert_eq!(parse("10000-01-01T00:00:00Z"), None);
+        assert_eq!(parse("9999-12-31T23:59:59Z"), Some(253_402_300_799));
+
+        for secs in [0, 951_782_400, 1_792_326_605, 4_107_542_399] {
+            let timestamp = Timestamp::from_secs(secs).expect("time should be in range");
+            assert_eq!(
+                timestamp.to_string().parse::<Timestamp>().ok(),
+                Some(timestamp)
+            );
+        }
+        assert_eq!(
+            Timestamp::from_secs(1_792_326_605).map(|t| t.to_string()),
+            Some("2026-10-18T12:30:05Z".into())
+        );
+    }
+
+    #[test]
+    pub fn latest_time() {
+        let latest = Timestamp::from_secs(253_402_300_799).expect("time should be in range");
+        assert_eq!(latest.to_string(), "9999-12-31T23:59:59Z");
+        assert_eq!(latest.to_string().parse::<Timestamp>().ok(), Some(latest));
+        assert_eq!("253402300799".parse::<Timestamp>().ok(), Some(latest));
+
+        assert_eq!(Timestamp::from_secs(253_402_300_800), None);
+        assert!("253402300800".parse::<Timestamp>().is_err());
+        assert!("999999999999".parse::<Timestamp>().is_err());
+    }
+}
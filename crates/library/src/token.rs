@@ -24,7 +24,15 @@ macro_rules! field_idents {
 
 field_idents!(
     info { category, tag },
-    unsorted { url, info, tag },
+    unsorted {
+        url,
+        info,
+        tag,
+        added,
+        modified,
+        visited,
+        id,
+    },
     category {
         id,
         desc,
@@ -33,3 +41,102 @@ field_idents!(
         sub,
     },
 );
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bookmark, Category, Info};
+    use bookmark_storage::{token::DELIM, Storeable};
+
+    const ALL: &[&str] = &[
+        info::CATEGORY,
+        info::TAG,
+        info::BEGIN,
+        info::END,
+        unsorted::URL,
+        unsorted::INFO,
+        unsorted::TAG,
+        unsorted::ADDED,
+        unsorted::MODIFIED,
+        unsorted::VISITED,
+        unsorted::ID,
+        unsorted::BEGIN,
+        unsorted::END,
+        category::ID,
+        category::DESC,
+        category::NAME,
+        category::IDENTIFIER,
+        category::SUB,
+        category::BEGIN,
+        category::END,
+        DELIM,
+    ];
+
+    fn content(token: &str) -> String {
+        format!("before {token} \\ after{token}")
+    }
+
+    #[test]
+    pub fn bookmark_round_trip() {
+        for token in ALL {
+            let value = content(token);
+            let line = Bookmark::new(&value, &value, [&value, &value].into_iter()).to_line();
+            let bookmark = Bookmark::from_string(line, None).expect("line should parse");
+
+            assert_eq!(bookmark.url(), value);
+            assert_eq!(bookmark.description(), value);
+            assert_eq!(bookmark.tags().collect::<Vec<_>>(), [&value, &value]);
+        }
+    }
+
+    #[test]
+    pub fn category_round_trip() {
+        for token in ALL {
+            let value = content(token);
+            let line = Category::new(
+                &value,
+                &value,
+                &value,
+                [&value].into_iter(),
+                [&value, &value].into_iter(),
+            )
+            .to_line();
+            let category = Category::from_string(line, None).expect("line should parse");
+
+            assert_eq!(category.id(), value);
+            assert_eq!(category.name(), value);
+            assert_eq!(category.description(), value);
+            assert_eq!(category.identifiers().collect::<Vec<_>>(), [&value]);
+            assert_eq!(
+                category.subcategories().collect::<Vec<_>>(),
+                [&value, &value]
+            );
+        }
+    }
+
+    #[test]
+    pub fn info_round_trip() {
+        for token in ALL {
+            let value = content(token);
+            let line = Info::new([&value, &value].into_iter(), [&value].into_iter()).to_line();
+            let info = Info::from_string(line, None).expect("line should parse");
+
+            assert_eq!(info.categories().collect::<Vec<_>>(), [&value, &value]);
+            assert_eq!(info.tags().collect::<Vec<_>>(), [&value]);
+        }
+    }
+
+    #[test]
+    pub fn unescaped_line_is_unchanged() {
+        let bookmark = Bookmark::from_string(
+            "<url> https://example.com <info> an example <tag> a <,> b",
+            None,
+        )
+        .expect("line should parse");
+
+        assert!(!bookmark.is_edited());
+        assert_eq!(bookmark.url(), "https://example.com");
+        assert_eq!(bookmark.description(), "an example");
+        assert_eq!(bookmark.tags().collect::<Vec<_>>(), ["a", "b"]);
+    }
+}
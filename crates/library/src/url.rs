@@ -0,0 +1,103 @@
+//! Helpers for inspecting the urls of bookmarks without fully parsing them.
+
+/// Get the host of a url, without any scheme, user info, port or leading "www.", urls without a
+/// scheme are treated as starting with the host.
+#[must_use]
+pub fn domain(url: &str) -> &str {
+    let url = url.trim();
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+
+    let host = match host.strip_prefix('[') {
+        // ipv6 addresses contain colons
+        Some(v6) => v6.split(']').next().unwrap_or_default(),
+        None => host.split(':').next().unwrap_or_default(),
+    };
+
+    host.strip_prefix("www.").unwrap_or(host)
+}
+
+/// Whether or not a query parameter only serves to track where a link was followed from.
+fn is_tracking(param: &str) -> bool {
+    let name = param.split('=').next().unwrap_or_default();
+    name.starts_with("utm_") || matches!(name, "fbclid" | "gclid" | "mc_cid" | "mc_eid")
+}
+
+/// Normalize a url such that urls leading to the same place are likely to be equal.
+///
+/// The scheme, a leading "www.", user info, the fragment, tracking parameters and trailing
+/// slashes are removed and the result is lowercased, as such it is only suitable for comparisons.
+#[must_use]
+pub fn normalize(url: &str) -> String {
+    let url = url.trim();
+    let url = url.split('#').next().unwrap_or_default();
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+
+    let (location, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let (authority, path) = location
+        .find('/')
+        .map_or((location, ""), |i| location.split_at(i));
+
+    let mut normalized = String::from(domain(authority));
+    if let Some((_, port)) = authority
+        .rsplit_once(':')
+        .filter(|(_, port)| port.bytes().all(|b| b.is_ascii_digit()) && !port.is_empty())
+    {
+        normalized.push(':');
+        normalized.push_str(port);
+    }
+    normalized.push_str(path.trim_end_matches('/'));
+
+    let mut params = query
+        .split('&')
+        .filter(|param| !param.is_empty() && !is_tracking(param));
+    if let Some(param) = params.next() {
+        normalized.push('?');
+        normalized.push_str(param);
+        for param in params {
+            normalized.push('&');
+            normalized.push_str(param);
+        }
+    }
+
+    normalized.to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn domains() {
+        assert_eq!(domain("https://www.example.com/a/b?c#d"), "example.com");
+        assert_eq!(
+            domain("http://user:pw@sub.example.com:8080"),
+            "sub.example.com"
+        );
+        assert_eq!(domain("example.com/path"), "example.com");
+        assert_eq!(domain("http://[::1]:80/"), "::1");
+        assert_eq!(domain("file:///home/user"), "");
+    }
+
+    #[test]
+    pub fn normalization() {
+        let normalized = normalize("https://www.Example.com/a/?utm_source=x&b=1&fbclid=y#top");
+        assert_eq!(normalized, "example.com/a?b=1");
+        for url in [
+            "http://example.com/a?b=1",
+            "example.com/a/?b=1&utm_medium=mail",
+            "HTTPS://EXAMPLE.COM/A?b=1#",
+        ] {
+            assert_eq!(normalize(url), normalized);
+        }
+
+        assert_eq!(normalize("https://example.com:8080/"), "example.com:8080");
+        assert_ne!(
+            normalize("https://example.com/a"),
+            normalize("https://example.com/b")
+        );
+    }
+}
@@ -16,75 +16,204 @@ pub struct Reference {
 impl Reference {}
 
 impl bookmark_storage::Storeable for Reference {
+    const PROPERTIES: &'static [&'static str] = &["name", "info", "children", "tags"];
+
     fn from_content_string(
         line: ContentString,
         line_num: Option<usize>,
     ) -> Result<Self, bookmark_storage::ParseErr> {
-        let err = || bookmark_storage::ParseErr::Line(Some(line.clone().to_string()), line_num);
+        let location = |column| bookmark_storage::Location::new(line_num, Some(column));
         let len = || line.len();
 
         use aho_corasick::AhoCorasick;
         use bookmark_storage::Lazy;
-        static AC: Lazy<AhoCorasick> =
-            Lazy::new(|| AhoCorasick::new(["<name>", "<children>", "<info>", "<tags>"]));
+        static TOKENS: &[&str] = &["<name>", "<children>", "<info>", "<tags>"];
+        static AC: Lazy<AhoCorasick> = Lazy::new(|| AhoCorasick::new(TOKENS));
+
+        let mut iter = AC.find_iter(&line).peekable();
+
+        // repeatable with index of field
+        let range = if let Some(mat) = iter.next_if(|mat| mat.pattern() == 0) {
+            let start = mat.end();
+            let end = iter.peek().map(|m| m.start()).unwrap_or_else(len);
+            if start > end {
+                return Err(bookmark_storage::ParseErr::InvalidRange {
+                    location: location(end),
+                    expected: TOKENS[0].into(),
+                    found: line[end..start].into(),
+                });
+            }
+            Some(start..end)
+        } else {
+            None
+        };
 
-        let mut iter = AC.find_iter(&line).enumerate().peekable();
+        // only for fields that are not optional
+        let Some(range) = range else {
+            return Err(match iter.peek() {
+                Some(mat) => bookmark_storage::ParseErr::UnexpectedToken {
+                    location: location(mat.start()),
+                    expected: TOKENS[0].into(),
+                    found: line[mat.start()..mat.end()].into(),
+                },
+                None => bookmark_storage::ParseErr::MissingToken {
+                    location: location(len()),
+                    expected: TOKENS[0].into(),
+                },
+            });
+        };
 
-        // completely repeatable
-        let (i, mat) = iter.next().ok_or_else(err)?;
-        let start = mat.end();
-        let end = iter.peek().map(|m| m.1.start()).unwrap_or_else(len);
-        if start > end || mat.pattern() != i {
-            return Err(err());
-        }
+        // unique for every match, single field pattern
+        let name: bookmark_storage::Field =
+            bookmark_storage::pattern_match::range_trim(&line, range).into();
+
+        // repeatable with index of field
+        let range = if let Some(mat) = iter.next_if(|mat| mat.pattern() == 1) {
+            let start = mat.end();
+            let end = iter.peek().map(|m| m.start()).unwrap_or_else(len);
+            if start > end {
+                return Err(bookmark_storage::ParseErr::InvalidRange {
+                    location: location(end),
+                    expected: TOKENS[1].into(),
+                    found: line[end..start].into(),
+                });
+            }
+            Some(start..end)
+        } else {
+            None
+        };
+
+        // only for fields that are not optional
+        let Some(range) = range else {
+            return Err(match iter.peek() {
+                Some(mat) => bookmark_storage::ParseErr::UnexpectedToken {
+                    location: location(mat.start()),
+                    expected: TOKENS[1].into(),
+                    found: line[mat.start()..mat.end()].into(),
+                },
+                None => bookmark_storage::ParseErr::MissingToken {
+                    location: location(len()),
+                    expected: TOKENS[1].into(),
+                },
+            });
+        };
 
         // unique for every match, single field pattern
-        let name = bookmark_storage::pattern_match::range_trim(&line, start..end).into();
-
-        // completely repeatable
-        let (i, mat) = iter.next().ok_or_else(err)?;
-        let start = mat.end();
-        let end = iter.peek().map(|m| m.1.start()).unwrap_or_else(len);
-        if start > end || mat.pattern() != i {
-            return Err(err());
-        }
+        let children: bookmark_storage::ListField =
+            bookmark_storage::pattern_match::split_list_field(&line[range.clone()])
+                .map(|f| f + range.start)
+                .collect();
+
+        // repeatable with index of field
+        let range = if let Some(mat) = iter.next_if(|mat| mat.pattern() == 2) {
+            let start = mat.end();
+            let end = iter.peek().map(|m| m.start()).unwrap_or_else(len);
+            if start > end {
+                return Err(bookmark_storage::ParseErr::InvalidRange {
+                    location: location(end),
+                    expected: TOKENS[2].into(),
+                    found: line[end..start].into(),
+                });
+            }
+            Some(start..end)
+        } else {
+            None
+        };
+
+        // only for fields that are not optional
+        let Some(range) = range else {
+            return Err(match iter.peek() {
+                Some(mat) => bookmark_storage::ParseErr::UnexpectedToken {
+                    location: location(mat.start()),
+                    expected: TOKENS[2].into(),
+                    found: line[mat.start()..mat.end()].into(),
+                },
+                None => bookmark_storage::ParseErr::MissingToken {
+                    location: location(len()),
+                    expected: TOKENS[2].into(),
+                },
+            });
+        };
 
         // unique for every match, single field pattern
-        let children = bookmark_storage::pattern_match::split_list_field(&line[start..end])
-            .map(|f| f + start)
-            .collect();
-
-        // completely repeatable
-        let (i, mat) = iter.next().ok_or_else(err)?;
-        let start = mat.end();
-        let end = iter.peek().map(|m| m.1.start()).unwrap_or_else(len);
-        if start > end || mat.pattern() != i {
-            return Err(err());
-        }
+        let info: bookmark_storage::Field =
+            bookmark_storage::pattern_match::range_trim(&line, range).into();
+
+        // repeatable with index of field
+        let range = if let Some(mat) = iter.next_if(|mat| mat.pattern() == 3) {
+            let start = mat.end();
+            let end = iter.peek().map(|m| m.start()).unwrap_or_else(len);
+            if start > end {
+                return Err(bookmark_storage::ParseErr::InvalidRange {
+                    location: location(end),
+                    expected: TOKENS[3].into(),
+                    found: line[end..start].into(),
+                });
+            }
+            Some(start..end)
+        } else {
+            None
+        };
+
+        // only for fields that are not optional
+        let Some(range) = range else {
+            return Err(match iter.peek() {
+                Some(mat) => bookmark_storage::ParseErr::UnexpectedToken {
+                    location: location(mat.start()),
+                    expected: TOKENS[3].into(),
+                    found: line[mat.start()..mat.end()].into(),
+                },
+                None => bookmark_storage::ParseErr::MissingToken {
+                    location: location(len()),
+                    expected: TOKENS[3].into(),
+                },
+            });
+        };
 
         // unique for every match, single field pattern
-        let info = bookmark_storage::pattern_match::range_trim(&line, start..end).into();
-
-        // completely repeatable
-        let (i, mat) = iter.next().ok_or_else(err)?;
-        let start = mat.end();
-        let end = iter.peek().map(|m| m.1.start()).unwrap_or_else(len);
-        if start > end || mat.pattern() != i {
-            return Err(err());
+        let tags: bookmark_storage::ListField =
+            bookmark_storage::pattern_match::split_list_field(&line[range.clone()])
+                .map(|f| f + range.start)
+                .collect();
+
+        // no tokens may remain once every field has been captured
+        if let Some(mat) = iter.next() {
+            return Err(bookmark_storage::ParseErr::UnexpectedToken {
+                location: location(mat.start()),
+                expected: "end of line".into(),
+                found: line[mat.start()..mat.end()].into(),
+            });
         }
 
-        // unique for every match, single field pattern
-        let tags = bookmark_storage::pattern_match::split_list_field(&line[start..end])
-            .map(|f| f + start)
-            .collect();
+        // only rebuild the line if some field contains escape sequences
+        let (line, name, children, info, tags) = if line.contains(bookmark_storage::token::ESCAPE) {
+            let mut unescaped = String::with_capacity(line.len());
+            let name = name.unescape_into(&line, &mut unescaped);
+            let children = children.unescape_into(&line, &mut unescaped);
+            let info = info.unescape_into(&line, &mut unescaped);
+            let tags = tags.unescape_into(&line, &mut unescaped);
+            (
+                ContentString::from_string(unescaped),
+                name,
+                children,
+                info,
+                tags,
+            )
+        } else {
+            (line, name, children, info, tags)
+        };
 
-        Ok(Self {
+        let mut parsed = Self {
             line,
             name,
             children,
             info,
             tags,
-        })
+        };
+        let overhead = parsed.waste();
+        parsed.line.set_overhead(overhead);
+
+        Ok(parsed)
     }
 
     fn to_line(&self) -> String {
@@ -95,6 +224,35 @@ impl bookmark_storage::Storeable for Reference {
         self.line.has_been_pushed_to()
     }
 
+    fn waste(&self) -> usize {
+        self.line.len()
+            - self.name.content_len()
+            - self.children.content_len()
+            - self.info.content_len()
+            - self.tags.content_len()
+    }
+
+    fn stale(&self) -> usize {
+        self.waste().saturating_sub(self.line.overhead())
+    }
+
+    fn stored_len(&self) -> usize {
+        self.line.len()
+    }
+
+    fn compact(&mut self) {
+        let mut compacted = String::with_capacity(self.stored_len() - self.waste());
+        let name = self.name.copy_into(&self.line, &mut compacted);
+        let children = self.children.copy_into(&self.line, &mut compacted);
+        let info = self.info.copy_into(&self.line, &mut compacted);
+        let tags = self.tags.copy_into(&self.line, &mut compacted);
+        self.name = name;
+        self.children = children;
+        self.info = info;
+        self.tags = tags;
+        self.line.replace_compacted(compacted);
+    }
+
     fn get(
         &self,
         property: &str,
@@ -147,17 +305,36 @@ impl Reference {
         info: &str,
         tags: impl Iterator<Item = impl AsRef<str>>,
     ) -> String {
-        format!(
-            "{} {} {} {} {} {} {} {}",
+        let mut out = String::new();
+        bookmark_storage::pattern_match::push_token(
+            &mut out,
             "<name>",
-            name,
+            &bookmark_storage::pattern_match::escape(name),
+        );
+        bookmark_storage::pattern_match::push_token(
+            &mut out,
             "<children>",
-            bookmark_storage::pattern_match::join_with_delim(children),
+            &bookmark_storage::pattern_match::join_with_delim(
+                children.map(|item| {
+                    bookmark_storage::pattern_match::escape(item.as_ref()).into_owned()
+                }),
+            ),
+        );
+        bookmark_storage::pattern_match::push_token(
+            &mut out,
             "<info>",
-            info,
+            &bookmark_storage::pattern_match::escape(info),
+        );
+        bookmark_storage::pattern_match::push_token(
+            &mut out,
             "<tags>",
-            bookmark_storage::pattern_match::join_with_delim(tags),
-        )
+            &bookmark_storage::pattern_match::join_with_delim(
+                tags.map(|item| {
+                    bookmark_storage::pattern_match::escape(item.as_ref()).into_owned()
+                }),
+            ),
+        );
+        out
     }
 
     pub fn new<'a>(
@@ -186,6 +363,7 @@ impl Reference {
 
     pub fn set_name(&mut self, name: &str) -> &mut Self {
         self.name = self.line.push(name).into();
+        bookmark_storage::Storeable::compact_if_wasteful(self);
 
         self
     }
@@ -200,6 +378,7 @@ impl Reference {
 
     pub fn set_info(&mut self, info: &str) -> &mut Self {
         self.info = self.line.push(info).into();
+        bookmark_storage::Storeable::compact_if_wasteful(self);
 
         self
     }
@@ -218,12 +397,14 @@ impl Reference {
         for item in children {
             self.children.push(self.line.push(item.as_ref()).into());
         }
+        bookmark_storage::Storeable::compact_if_wasteful(self);
 
         self
     }
 
     pub fn push_child(&mut self, child: &str) -> &mut Self {
         self.children.push(self.line.push(child).into());
+        bookmark_storage::Storeable::compact_if_wasteful(self);
 
         self
     }
@@ -242,12 +423,14 @@ impl Reference {
         for item in tags {
             self.tags.push(self.line.push(item.as_ref()).into());
         }
+        bookmark_storage::Storeable::compact_if_wasteful(self);
 
         self
     }
 
     pub fn push_tag(&mut self, tag: &str) -> &mut Self {
         self.tags.push(self.line.push(tag).into());
+        bookmark_storage::Storeable::compact_if_wasteful(self);
 
         self
     }
@@ -1,12 +1,13 @@
 use crate::Field;
 use std::{fmt::Display, ops::Range, sync::Arc};
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 /// String that keeps track of whether or not it has been appended to
 /// used by Storeable derives to store string data.
 pub struct ContentString {
     is_appended_to: bool,
     content: Option<Content>,
+    overhead: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -34,6 +35,16 @@ impl Default for Content {
     }
 }
 
+impl Default for ContentString {
+    fn default() -> Self {
+        Self {
+            is_appended_to: false,
+            overhead: 0,
+            content: Some(Content::default()),
+        }
+    }
+}
+
 impl ContentString {
     /// Create a new [`ContentString`], it is empty ad marked as not appended to.
     #[must_use]
@@ -46,6 +57,7 @@ impl ContentString {
     pub fn from_string(value: String) -> Self {
         ContentString {
             is_appended_to: false,
+            overhead: 0,
             content: Some(Content::from_string(value)),
         }
     }
@@ -59,6 +71,7 @@ impl ContentString {
         assert!(rc.get(range.clone()).is_some());
         Self {
             is_appended_to: false,
+            overhead: 0,
             content: Some(Content::RcRange(rc, range)),
         }
     }
@@ -69,8 +82,17 @@ impl ContentString {
         self.is_appended_to
     }
 
+    /// Mark the string as appended to without appending anything, used when a field not stored in
+    /// the string changes.
+    pub fn touch(&mut self) {
+        self.is_appended_to = true;
+    }
+
     /// Push some more content onto the string and get the location
     /// of the pushed content.
+    ///
+    /// # Panics
+    /// If the content has been taken, which should never happen.
     pub fn push(&mut self, content: &str) -> Range<usize> {
         let mut string = self
             .content
@@ -90,6 +112,9 @@ impl ContentString {
     }
 
     /// Get content as a string slice.
+    ///
+    /// # Panics
+    /// If the content has been taken, which should never happen.
     #[must_use]
     pub fn as_str(&self) -> &str {
         match self
@@ -102,6 +127,24 @@ impl ContentString {
         }
     }
 
+    /// Replace the content with a compacted version of it, whether or not the string has been
+    /// appended to is kept.
+    pub fn replace_compacted(&mut self, content: String) {
+        self.content = Some(Content::from_string(content));
+        self.overhead = 0;
+    }
+
+    /// Amount of bytes that were not content of any field when parsed, such as tokens.
+    #[must_use]
+    pub fn overhead(&self) -> usize {
+        self.overhead
+    }
+
+    /// Set the amount of bytes that were not content of any field when parsed.
+    pub fn set_overhead(&mut self, overhead: usize) {
+        self.overhead = overhead;
+    }
+
     /// Push multiple pieces of content and get their locations.
     pub fn extend<'a>(
         &mut self,
@@ -1,3 +1,4 @@
+use crate::pattern_match;
 use std::ops::{Add, AddAssign, Range};
 
 #[derive(Debug, Clone)]
@@ -16,6 +17,30 @@ impl Field {
     pub fn get<'a>(&self, from: &'a str) -> &'a str {
         &from[self.0.clone()]
     }
+
+    /// Get the length in bytes of the content of the field.
+    #[must_use]
+    pub fn content_len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Append the content of the field in a string slice to a string, getting the location of
+    /// the content in that string.
+    #[must_use]
+    pub fn copy_into(&self, from: &str, into: &mut String) -> Self {
+        let start = into.len();
+        *into += self.get(from);
+        Self::new(start, into.len())
+    }
+
+    /// Append the unescaped content of the field in a string slice to a string, getting the
+    /// location of the content in that string.
+    #[must_use]
+    pub fn unescape_into(&self, from: &str, into: &mut String) -> Self {
+        let start = into.len();
+        *into += &pattern_match::unescape(self.get(from));
+        Self::new(start, into.len())
+    }
 }
 
 impl Add<usize> for Field {
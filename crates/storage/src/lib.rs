@@ -16,9 +16,11 @@ mod content_string;
 mod field;
 mod list_field;
 mod load;
+mod migration;
 mod parse_err;
 mod property;
 mod save;
+mod scan;
 mod section;
 mod storeable;
 
@@ -33,12 +35,15 @@ pub use content_string::ContentString;
 pub use field::Field;
 pub use list_field::ListField;
 pub use load::from as load_from;
-pub use load::load;
-pub use parse_err::ParseErr;
+pub use load::from_lenient as load_from_lenient;
+pub use load::{load, load_lenient, parse_parallel, Quarantined, Report as LoadReport};
+pub use migration::{read_header, write_header, Migration, Migrations, Upgrade};
+pub use parse_err::{Location, ParseErr};
 pub use property::{Error as PropertyErr, Property};
-pub use save::save;
+pub use save::{backup_path, save, save_atomic};
+pub use scan::{scan, scan_shared, Collector, SectionSink, UnknownSection};
 pub use section::Section;
-pub use storeable::Storeable;
+pub use storeable::{Storeable, COMPACT_MIN_WASTE};
 
 /// Trait for types that imlement both [Section] ans [Storeable].
 pub trait Listed: Storeable + Section {}
@@ -61,4 +61,24 @@ impl ListField {
     pub fn get<'a>(&'a self, from: &'a str) -> impl DoubleEndedIterator<Item = &'a str> {
         self.0.iter().map(|f| f.get(from))
     }
+
+    /// Get the total length in bytes of the contents of the [`ListField`].
+    #[must_use]
+    pub fn content_len(&self) -> usize {
+        self.0.iter().map(Field::content_len).sum()
+    }
+
+    /// Append the contents of the [`ListField`] in a string slice to a string, getting the
+    /// locations of the contents in that string.
+    #[must_use]
+    pub fn copy_into(&self, from: &str, into: &mut String) -> Self {
+        self.0.iter().map(|f| f.copy_into(from, into)).collect()
+    }
+
+    /// Append the unescaped contents of the [`ListField`] in a string slice to a string, getting
+    /// the locations of the contents in that string.
+    #[must_use]
+    pub fn unescape_into(&self, from: &str, into: &mut String) -> Self {
+        self.0.iter().map(|f| f.unescape_into(from, into)).collect()
+    }
 }
@@ -1,5 +1,91 @@
-use crate::{Listed, ParseErr};
-use std::io::{self, BufRead, BufReader, Read};
+use crate::{ContentString, Listed, ParseErr};
+use std::{
+    fmt::Display,
+    io::{self, BufRead, BufReader, Read},
+    path::Path,
+};
+
+/// A line that could not be parsed by a lenient load.
+#[derive(Clone, Debug)]
+pub struct Quarantined {
+    /// Zero based index of the line.
+    pub line_num: usize,
+    /// Content of the line.
+    pub line: String,
+    /// Why the line could not be parsed.
+    pub error: ParseErr,
+}
+
+impl Display for Quarantined {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}, line: {}", self.error, self.line)
+    }
+}
+
+/// Items parsed by a lenient load alongside the lines that could not be parsed.
+#[derive(Debug)]
+pub struct Report<T> {
+    /// Successfully parsed items.
+    pub items: Vec<T>,
+    /// Lines that could not be parsed.
+    pub quarantined: Vec<Quarantined>,
+}
+
+impl<T> Default for Report<T> {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            quarantined: Vec::new(),
+        }
+    }
+}
+
+impl<T> Report<T>
+where
+    T: Listed,
+{
+    /// Parse a line, adding the item to the report or quarantining the line if `lenient` is
+    /// true.
+    pub(crate) fn parse_line(
+        &mut self,
+        line: ContentString,
+        line_num: usize,
+        lenient: bool,
+    ) -> Result<(), ParseErr> {
+        match T::from_content_string(line.clone(), Some(line_num)) {
+            Ok(item) => self.items.push(item),
+            Err(error) if lenient => self.quarantined.push(Quarantined {
+                line_num,
+                line: line.into(),
+                error,
+            }),
+            Err(error) => return Err(error),
+        }
+        Ok(())
+    }
+}
+
+impl<T> Report<T> {
+    /// Returns true if no lines were quarantined.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.quarantined.is_empty()
+    }
+
+    /// Set the path of the file the lines were read from on all quarantined lines.
+    #[must_use]
+    pub fn with_path(mut self, path: impl AsRef<Path>) -> Self {
+        self.quarantined = self
+            .quarantined
+            .into_iter()
+            .map(|q| Quarantined {
+                error: q.error.with_path(path.as_ref()),
+                ..q
+            })
+            .collect();
+        self
+    }
+}
 
 /// Load occurances of a [Listed] type from a [Read].
 ///
@@ -43,3 +129,101 @@ where
 
     Ok(out)
 }
+
+/// Load occurances of a [Listed] type from a [Read], lines that cannot be parsed are reported
+/// instead of failing the load.
+///
+/// # Errors
+/// If a line cannot be read.
+pub fn load_lenient<T>(reader: &mut impl Read) -> Result<Report<T>, ParseErr>
+where
+    T: Listed,
+{
+    let reader = BufReader::new(reader);
+    from_lenient(reader.lines().enumerate())
+}
+
+/// Load occurances of a [Listed] type from an enumerated iterator of line parses, lines that
+/// cannot be parsed are reported instead of failing the load.
+///
+/// # Errors
+/// If any of the line reads in the passed iterator is an error.
+pub fn from_lenient<T>(
+    mut reader: impl Iterator<Item = (usize, io::Result<String>)>,
+) -> Result<Report<T>, ParseErr>
+where
+    T: Listed,
+{
+    for (_, result) in reader.by_ref() {
+        if result? == T::TOKEN_BEGIN {
+            break;
+        }
+    }
+
+    let mut report = Report::default();
+
+    for (i, result) in reader.by_ref() {
+        let line = result?;
+        if line == T::TOKEN_END {
+            break;
+        }
+
+        report.parse_line(line.into(), i, true)?;
+    }
+
+    Ok(report)
+}
+
+/// Parse lines of a [Listed] type split into chunks across `threads` threads, the order of the
+/// lines is kept in the resulting report.
+///
+/// # Errors
+/// If `lenient` is false and any line cannot be parsed, the first such line is reported.
+pub fn parse_parallel<T>(
+    lines: Vec<(usize, ContentString)>,
+    threads: usize,
+    lenient: bool,
+) -> Result<Report<T>, ParseErr>
+where
+    T: Listed + Send,
+{
+    let chunk_size = lines.len().div_ceil(threads.max(1)).max(1);
+
+    let mut chunks = Vec::with_capacity(threads);
+    let mut lines = lines.into_iter().peekable();
+    while lines.peek().is_some() {
+        chunks.push(lines.by_ref().take(chunk_size).collect::<Vec<_>>());
+    }
+
+    let parsed = std::thread::scope(|scope| {
+        chunks
+            .into_iter()
+            .map(|chunk| {
+                scope.spawn(move || {
+                    let mut report = Report::default();
+                    report.items.reserve(chunk.len());
+                    for (line_num, line) in chunk {
+                        report.parse_line(line, line_num, lenient)?;
+                    }
+                    Ok::<_, ParseErr>(report)
+                })
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|payload| std::panic::resume_unwind(payload))
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let mut report = Report::default();
+    for chunk in parsed {
+        let chunk = chunk?;
+        report.items.extend(chunk.items);
+        report.quarantined.extend(chunk.quarantined);
+    }
+
+    Ok(report)
+}
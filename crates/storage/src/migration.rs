@@ -0,0 +1,250 @@
+use crate::{token, Location, ParseErr, Section};
+use std::{
+    io::{self, Write},
+    iter::Peekable,
+};
+
+/// Function upgrading a line by one format version.
+pub type Migration = fn(String) -> String;
+
+#[derive(Clone, Copy, Debug)]
+struct Step {
+    begin: &'static str,
+    end: &'static str,
+    from: u32,
+    migrate: Migration,
+}
+
+/// Registry of migrations used to upgrade lines of older format versions when loading.
+#[derive(Clone, Debug)]
+pub struct Migrations {
+    version: u32,
+    steps: Vec<Step>,
+}
+
+impl Migrations {
+    /// Create a new registry for the given current format version, without any migrations.
+    #[must_use]
+    pub fn new(version: u32) -> Self {
+        Self {
+            version,
+            steps: Vec::new(),
+        }
+    }
+
+    /// Register a migration upgrading lines in the section of `T` from version `from` to
+    /// version `from + 1`, migrations are applied in order of the version they upgrade from.
+    #[must_use]
+    pub fn register<T>(mut self, from: u32, migrate: Migration) -> Self
+    where
+        T: Section,
+    {
+        self.steps.push(Step {
+            begin: T::TOKEN_BEGIN,
+            end: T::TOKEN_END,
+            from,
+            migrate,
+        });
+        self.steps.sort_by_key(|step| step.from);
+        self
+    }
+
+    /// The current format version.
+    #[must_use]
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Read the header of an enumerated iterator of lines, if any, and wrap it such that lines
+    /// are upgraded to the current format version. Files without a header are assumed to be of
+    /// version 0.
+    ///
+    /// # Errors
+    /// If the header is malformed or states a version newer than the current one.
+    pub fn upgrade<I>(&self, lines: I) -> Result<Upgrade<'_, Peekable<I>>, ParseErr>
+    where
+        I: Iterator<Item = (usize, io::Result<String>)>,
+    {
+        let mut lines = lines.peekable();
+
+        let version = match lines.peek() {
+            Some((i, Ok(line))) => match read_header(line, Some(*i))? {
+                Some(version) => {
+                    lines.next();
+                    version
+                }
+                None => 0,
+            },
+            _ => 0,
+        };
+
+        if version > self.version {
+            return Err(ParseErr::Other(format!(
+                "file format version {version} is newer than the supported version {}",
+                self.version
+            )));
+        }
+
+        Ok(Upgrade {
+            lines,
+            steps: self
+                .steps
+                .iter()
+                .filter(|step| step.from >= version && step.from < self.version)
+                .collect(),
+            section: None,
+        })
+    }
+}
+
+/// Iterator upgrading lines to the current format version, created by [`Migrations::upgrade`].
+#[derive(Debug)]
+pub struct Upgrade<'a, I> {
+    lines: I,
+    steps: Vec<&'a Step>,
+    section: Option<(&'static str, &'static str)>,
+}
+
+impl<I> Iterator for Upgrade<'_, I>
+where
+    I: Iterator<Item = (usize, io::Result<String>)>,
+{
+    type Item = (usize, io::Result<String>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (i, line) = self.lines.next()?;
+
+        if self.steps.is_empty() {
+            return Some((i, line));
+        }
+
+        let Ok(line) = line else {
+            return Some((i, line));
+        };
+
+        if let Some((begin, end)) = self.section {
+            if line == end {
+                self.section = None;
+                return Some((i, Ok(line)));
+            }
+
+            let line = self
+                .steps
+                .iter()
+                .filter(|step| step.begin == begin)
+                .fold(line, |line, step| (step.migrate)(line));
+
+            return Some((i, Ok(line)));
+        }
+
+        if let Some(step) = self.steps.iter().find(|step| step.begin == line) {
+            self.section = Some((step.begin, step.end));
+        }
+
+        Some((i, Ok(line)))
+    }
+}
+
+/// Write a header line stating the format version.
+///
+/// # Errors
+/// If the write fails.
+pub fn write_header(writer: &mut impl Write, version: u32) -> io::Result<()> {
+    writeln!(writer, "{} {version}", token::VERSION)
+}
+
+/// Read the format version from a header line, giving `None` if the line is not a header.
+///
+/// # Errors
+/// If the line is a header but the version cannot be parsed.
+pub fn read_header(line: &str, line_num: Option<usize>) -> Result<Option<u32>, ParseErr> {
+    let Some(version) = line.strip_prefix(token::VERSION) else {
+        return Ok(None);
+    };
+
+    version
+        .trim()
+        .parse()
+        .map(Some)
+        .map_err(|_| ParseErr::Line {
+            location: Location::new(line_num, Some(token::VERSION.len())),
+            found: line.into(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct A;
+    impl Section for A {
+        const ITEM_NAME: &'static str = "a";
+        const TOKEN_BEGIN: &'static str = "#A_BEGIN";
+        const TOKEN_END: &'static str = "#A_END";
+    }
+
+    struct B;
+    impl Section for B {
+        const ITEM_NAME: &'static str = "b";
+        const TOKEN_BEGIN: &'static str = "#B_BEGIN";
+        const TOKEN_END: &'static str = "#B_END";
+    }
+
+    fn upgraded(migrations: &Migrations, content: &str) -> Result<Vec<String>, ParseErr> {
+        Ok(migrations
+            .upgrade(content.lines().map(|l| Ok(l.to_string())).enumerate())?
+            .map(|(_, line)| line.expect("lines should be ok"))
+            .collect())
+    }
+
+    #[test]
+    pub fn upgrade() {
+        let migrations = Migrations::new(2)
+            .register::<A>(1, |line| line + " two")
+            .register::<A>(0, |line| line + " one")
+            .register::<B>(0, |line| line.to_uppercase());
+
+        assert_eq!(
+            upgraded(&migrations, "#A_BEGIN\na\n#A_END\n#B_BEGIN\nb\n#B_END\nc")
+                .expect("legacy file should upgrade"),
+            [
+                "#A_BEGIN",
+                "a one two",
+                "#A_END",
+                "#B_BEGIN",
+                "B",
+                "#B_END",
+                "c"
+            ]
+        );
+
+        assert_eq!(
+            upgraded(
+                &migrations,
+                "#VERSION 1\n#A_BEGIN\na\n#A_END\n#B_BEGIN\nb\n#B_END"
+            )
+            .expect("version 1 file should upgrade"),
+            ["#A_BEGIN", "a two", "#A_END", "#B_BEGIN", "b", "#B_END"]
+        );
+
+        assert_eq!(
+            upgraded(&migrations, "#VERSION 2\n#A_BEGIN\na\n#A_END")
+                .expect("current file should load"),
+            ["#A_BEGIN", "a", "#A_END"]
+        );
+
+        upgraded(&migrations, "#VERSION 3\n#A_BEGIN\na\n#A_END")
+            .expect_err("newer file should fail");
+        upgraded(&migrations, "#VERSION x").expect_err("malformed header should fail");
+    }
+
+    #[test]
+    pub fn header_round_trip() {
+        let mut header = Vec::new();
+        write_header(&mut header, 7).expect("write to vec should succeed");
+        let header = String::from_utf8(header).expect("header should be utf-8");
+
+        assert_eq!(read_header(header.trim_end(), None).ok(), Some(Some(7)));
+        assert_eq!(read_header("#A_BEGIN", None).ok(), Some(None));
+    }
+}
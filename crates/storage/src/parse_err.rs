@@ -1,21 +1,151 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
 use thiserror::Error;
 
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+/// Where in some content a parse issue occurred, any part of which may be unknown.
+///
+/// When displayed lines and columns are one based, as most editors expect.
+pub struct Location {
+    /// Path of the file the content was read from.
+    pub path: Option<PathBuf>,
+    /// Zero based index of the line in the file.
+    pub line: Option<usize>,
+    /// Zero based byte offset into the line.
+    pub column: Option<usize>,
+}
+
+impl Location {
+    /// Create a new [Location] without a path.
+    #[must_use]
+    pub fn new(line: Option<usize>, column: Option<usize>) -> Self {
+        Self {
+            path: None,
+            line,
+            column,
+        }
+    }
+
+    /// Set the path of the [Location].
+    #[must_use]
+    pub fn with_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.path {
+            Some(ref path) => write!(f, "{}", path.display())?,
+            None => write!(f, "<unknown>")?,
+        }
+        match self.line {
+            Some(line) => write!(f, ":{}", line + 1)?,
+            None => write!(f, ":<unknown>")?,
+        }
+        match self.column {
+            Some(column) => write!(f, ":{}", column + 1),
+            None => write!(f, ":<unknown>"),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Error)]
 /// Errors representing failure to parse some content.
 pub enum ParseErr {
-    /// If some line was unsuccessfully parsed optionally has which line and/or a
-    /// message.
-    #[error(
-        "could not parse line {}: {}",
-        .1.map(|v| v.to_string()).unwrap_or_else(|| String::from("<unknown>")),
-        .0.clone().unwrap_or_else(|| String::from("<unknown>")),
-    )]
-    Line(Option<String>, Option<usize>),
+    /// If the line ended before an expected token was found.
+    #[error("{location}: expected token {expected} but reached end of line")]
+    MissingToken {
+        /// Where the token was expected.
+        location: Location,
+        /// The token that was expected.
+        expected: String,
+    },
+    /// If a token was found where another one was expected.
+    #[error("{location}: expected token {expected} but found {found}")]
+    UnexpectedToken {
+        /// Where the found token begins.
+        location: Location,
+        /// The token that was expected.
+        expected: String,
+        /// The token that was found.
+        found: String,
+    },
+    /// If the content following a token ends before it begins.
+    #[error("{location}: content of token {expected} overlaps \"{found}\"")]
+    InvalidRange {
+        /// Where the content ends.
+        location: Location,
+        /// The token the content belongs to.
+        expected: String,
+        /// The overlapping text.
+        found: String,
+    },
+    /// If the content following a token could not be parsed as the type of the field.
+    #[error("{location}: invalid value \"{found}\" for token {expected}")]
+    InvalidValue {
+        /// Where the content begins.
+        location: Location,
+        /// The token the content belongs to.
+        expected: String,
+        /// The content.
+        found: String,
+    },
+    /// If some line was unsuccessfully parsed for no more specific reason.
+    #[error("{location}: could not parse line \"{found}\"")]
+    Line {
+        /// Where the line is.
+        location: Location,
+        /// Content of the line.
+        found: String,
+    },
     /// Some other issue parsing with a message.
     #[error("parse issue: {0}")]
     Other(String),
 }
 
+impl ParseErr {
+    /// Get the [Location] of the issue, if it has one.
+    #[must_use]
+    pub fn location(&self) -> Option<&Location> {
+        match self {
+            Self::MissingToken { location, .. }
+            | Self::UnexpectedToken { location, .. }
+            | Self::InvalidRange { location, .. }
+            | Self::InvalidValue { location, .. }
+            | Self::Line { location, .. } => Some(location),
+            Self::Other(_) => None,
+        }
+    }
+
+    /// Set the path of the file the issue occurred in, if the issue has a [Location].
+    #[must_use]
+    pub fn with_path(mut self, path: impl AsRef<Path>) -> Self {
+        match self {
+            Self::MissingToken {
+                ref mut location, ..
+            }
+            | Self::UnexpectedToken {
+                ref mut location, ..
+            }
+            | Self::InvalidRange {
+                ref mut location, ..
+            }
+            | Self::InvalidValue {
+                ref mut location, ..
+            }
+            | Self::Line {
+                ref mut location, ..
+            } => location.path = Some(path.as_ref().into()),
+            Self::Other(_) => (),
+        }
+        self
+    }
+}
+
 impl From<std::io::Error> for ParseErr {
     fn from(err: std::io::Error) -> Self {
         ParseErr::Other(format!("{err}"))
@@ -1,5 +1,5 @@
 use crate::{token, Field};
-use std::{convert::TryInto, ops::Range};
+use std::{borrow::Cow, convert::TryInto, ops::Range};
 
 /// Split a string slice into a vector of string slices with the delimiter [`token::DELIM`].
 ///
@@ -92,8 +92,8 @@ pub fn range_trim(source: &str, location: Range<usize>) -> Range<usize> {
 /// Join and iterator of string slices into a single string delimited by [`token::DELIM`].
 #[must_use]
 pub fn join_with_delim(mut fields: impl Iterator<Item = impl AsRef<str>>) -> String {
-    use once_cell::sync::Lazy;
-    static DELIM: Lazy<String> = Lazy::new(|| format!(" {} ", token::DELIM));
+    use std::sync::LazyLock;
+    static DELIM: LazyLock<String> = LazyLock::new(|| format!(" {} ", token::DELIM));
 
     let mut out = String::new();
 
@@ -109,6 +109,84 @@ pub fn join_with_delim(mut fields: impl Iterator<Item = impl AsRef<str>>) -> Str
     out
 }
 
+/// Escape a string slice such that it may be stored as field content without being
+/// mistaken for a token or [`token::DELIM`], reversed by [`unescape`].
+#[must_use]
+pub fn escape(value: &str) -> Cow<'_, str> {
+    if !value.contains([token::ESCAPE, '<', '\n', '\r']) {
+        return Cow::Borrowed(value);
+    }
+
+    let mut out = String::with_capacity(value.len() + 8);
+    for c in value.chars() {
+        match c {
+            token::ESCAPE => {
+                out.push(token::ESCAPE);
+                out.push(token::ESCAPE);
+            }
+            '<' => {
+                out.push(token::ESCAPE);
+                out.push('l');
+            }
+            '\n' => {
+                out.push(token::ESCAPE);
+                out.push('n');
+            }
+            '\r' => {
+                out.push(token::ESCAPE);
+                out.push('r');
+            }
+            c => out.push(c),
+        }
+    }
+
+    Cow::Owned(out)
+}
+
+/// Undo the escaping performed by [`escape`].
+///
+/// Unknown escape sequences are kept as they are, such that content written before escaping was
+/// introduced is mostly unaffected.
+#[must_use]
+pub fn unescape(value: &str) -> Cow<'_, str> {
+    if !value.contains(token::ESCAPE) {
+        return Cow::Borrowed(value);
+    }
+
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != token::ESCAPE {
+            out.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some(token::ESCAPE) | None => out.push(token::ESCAPE),
+            Some('l') => out.push('<'),
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some(other) => {
+                out.push(token::ESCAPE);
+                out.push(other);
+            }
+        }
+    }
+
+    Cow::Owned(out)
+}
+
+/// Push a token followed by its already escaped value onto a line, separated from any previous
+/// content by a space.
+pub fn push_token(line: &mut String, token: &str, value: &str) {
+    if !line.is_empty() {
+        line.push(' ');
+    }
+    line.push_str(token);
+    line.push(' ');
+    line.push_str(value);
+}
+
 /// Write contents of a string slice iterator delimited by [`token::DELIM`].
 ///
 /// # Errors
@@ -166,6 +244,28 @@ mod tests {
         );
     }
 
+    #[test]
+    pub fn escape_round_trip() {
+        for value in [
+            "",
+            "plain text",
+            "<url> <info> <tag>",
+            "a <,> b",
+            "back\\slash \\l not an escape",
+            "line\nbreak\r\n",
+            "\\",
+        ] {
+            let escaped = escape(value);
+            assert!(!escaped.contains('<'));
+            assert!(!escaped.contains('\n'));
+            assert_eq!(unescape(&escaped), value);
+        }
+
+        assert!(matches!(escape("nothing to do"), Cow::Borrowed(_)));
+        assert_eq!(unescape(r"unknown \d escape"), r"unknown \d escape");
+        assert_eq!(unescape("trailing \\"), "trailing \\");
+    }
+
     #[test]
     pub fn field_split_basic() {
         let field = "a <,> b <,> c";
@@ -6,9 +6,19 @@ pub enum Error {
     /// If the property does not exist and a message.
     #[error("property {0} does not exist as expected type of property")]
     DoesNotExist(String),
+    /// If the value cannot be parsed as the type of the property.
+    #[error("value \"{value}\" is invalid for property {property}, {reason}")]
+    InvalidValue {
+        /// The property.
+        property: String,
+        /// The invalid value.
+        value: String,
+        /// Why the value is invalid.
+        reason: String,
+    },
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 /// A property may be either a list of values or a single value
 /// however the same property is always of the same type.
 pub enum Property {
@@ -1,4 +1,9 @@
-use std::io::{self, Write};
+use std::{
+    ffi::OsString,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
 
 use crate::{Listed, Storeable};
 
@@ -20,3 +25,109 @@ where
 
     Ok(())
 }
+
+fn with_suffix(path: &Path, suffix: impl AsRef<str>) -> PathBuf {
+    let mut path = OsString::from(path);
+    path.push(suffix.as_ref());
+    path.into()
+}
+
+/// Get the path of the nth backup of a file, the most recent backup being 1.
+#[must_use]
+pub fn backup_path(path: &Path, n: usize) -> PathBuf {
+    with_suffix(path, format!(".{n}"))
+}
+
+/// Shift existing backups of a file one step and copy the file to the first backup, keeping at
+/// most `backups` backups.
+fn rotate_backups(path: &Path, backups: usize) -> io::Result<()> {
+    for n in (1..backups).rev() {
+        let from = backup_path(path, n);
+        if from.exists() {
+            fs::rename(from, backup_path(path, n + 1))?;
+        }
+    }
+
+    fs::copy(path, backup_path(path, 1))?;
+
+    Ok(())
+}
+
+/// Replace the content of a file without ever leaving it partially written. The content is
+/// written to a temporary sibling file which is synced and renamed over the target, keeping up to
+/// `backups` rotated copies of the previous content as `path.1`, `path.2` and so on.
+///
+/// # Errors
+/// If any write, sync or rename fails, the target is then left untouched.
+pub fn save_atomic(
+    path: impl AsRef<Path>,
+    backups: usize,
+    write: impl FnOnce(&mut BufWriter<File>) -> io::Result<()>,
+) -> io::Result<()> {
+    let path = path.as_ref();
+    let temp = with_suffix(path, format!(".{}.tmp", std::process::id()));
+
+    let written = File::create(&temp).and_then(|file| {
+        let mut writer = BufWriter::new(file);
+        write(&mut writer)?;
+        writer
+            .into_inner()
+            .map_err(io::IntoInnerError::into_error)?
+            .sync_all()
+    });
+
+    if let Err(err) = written {
+        fs::remove_file(&temp).ok();
+        return Err(err);
+    }
+
+    if backups > 0 && path.exists() {
+        rotate_backups(path, backups)?;
+    }
+
+    fs::rename(&temp, path)?;
+
+    // Persist the rename itself, directories cannot be opened for syncing on all platforms.
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        if let Ok(dir) = File::open(dir) {
+            dir.sync_all().ok();
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn atomic_backups() {
+        let dir =
+            std::env::temp_dir().join(format!("bookmark-storage-save-{}", std::process::id()));
+        fs::create_dir_all(&dir).expect("temp dir should be creatable");
+        let path = dir.join("bookmarks.txt");
+
+        for content in ["a", "b", "c", "d"] {
+            save_atomic(&path, 2, |writer| write!(writer, "{content}"))
+                .expect("save should succeed");
+        }
+
+        let read = |path: PathBuf| fs::read_to_string(path).expect("file should be readable");
+        assert_eq!(read(path.clone()), "d");
+        assert_eq!(read(backup_path(&path, 1)), "c");
+        assert_eq!(read(backup_path(&path, 2)), "b");
+        assert!(!backup_path(&path, 3).exists());
+
+        save_atomic(&path, 2, |_| Err(io::Error::other("failed write")))
+            .expect_err("failed write should fail save");
+        assert_eq!(read(path.clone()), "d");
+        assert_eq!(read(backup_path(&path, 1)), "c");
+        assert_eq!(
+            fs::read_dir(&dir).expect("dir should be readable").count(),
+            3
+        );
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
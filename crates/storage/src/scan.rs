@@ -0,0 +1,259 @@
+use crate::{
+    load::{parse_parallel, Report},
+    read_header, ContentString, Listed, Migrations, ParseErr,
+};
+use std::{fmt::Display, io, ops::Range, path::Path, sync::Arc};
+
+/// Destination of the lines of a section found by [`scan`].
+pub trait SectionSink {
+    /// Content of the line signaling the beginning of the section.
+    fn token_begin(&self) -> &'static str;
+
+    /// Content of the line signaling the end of the section.
+    fn token_end(&self) -> &'static str;
+
+    /// Called when the beginning of a section is found.
+    fn begin_section(&mut self) {}
+
+    /// Handle a line of the section.
+    ///
+    /// # Errors
+    /// If the line cannot be handled and the scan should be aborted.
+    fn push_line(&mut self, line: ContentString, line_num: usize) -> Result<(), ParseErr>;
+}
+
+/// [`SectionSink`] parsing the lines of all sections of a [Listed] type.
+#[derive(Debug)]
+pub struct Collector<T> {
+    report: Report<T>,
+    lenient: bool,
+    sections: usize,
+    threads: usize,
+    pending: Vec<(usize, ContentString)>,
+}
+
+impl<T> Collector<T> {
+    /// Create a new [Collector], if `lenient` is true lines that cannot be parsed are
+    /// quarantined instead of aborting the scan.
+    #[must_use]
+    pub fn new(lenient: bool) -> Self {
+        Self {
+            report: Report::default(),
+            lenient,
+            sections: 0,
+            threads: 1,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Parse the collected lines on the given amount of threads once all have been collected,
+    /// instead of parsing each line as it is collected.
+    #[must_use]
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    /// Amount of sections collected from.
+    #[must_use]
+    pub fn sections(&self) -> usize {
+        self.sections
+    }
+}
+
+impl<T> Collector<T>
+where
+    T: Listed + Send,
+{
+    /// Get the items and quarantined lines collected.
+    ///
+    /// # Errors
+    /// If lines are parsed on multiple threads, the collector is not lenient and a line cannot
+    /// be parsed.
+    pub fn into_report(self) -> Result<Report<T>, ParseErr> {
+        if self.pending.is_empty() {
+            Ok(self.report)
+        } else {
+            parse_parallel(self.pending, self.threads, self.lenient)
+        }
+    }
+}
+
+impl<T> SectionSink for Collector<T>
+where
+    T: Listed,
+{
+    fn token_begin(&self) -> &'static str {
+        T::TOKEN_BEGIN
+    }
+
+    fn token_end(&self) -> &'static str {
+        T::TOKEN_END
+    }
+
+    fn begin_section(&mut self) {
+        self.sections += 1;
+    }
+
+    fn push_line(&mut self, line: ContentString, line_num: usize) -> Result<(), ParseErr> {
+        if self.threads > 1 {
+            self.pending.push((line_num, line));
+            Ok(())
+        } else {
+            self.report.parse_line(line, line_num, self.lenient)
+        }
+    }
+}
+
+/// A section no [`SectionSink`] was given for, skipped by [`scan`].
+#[derive(Clone, Debug)]
+pub struct UnknownSection {
+    /// Zero based index of the line the section begins on.
+    pub line_num: usize,
+    /// Content of the line the section begins on.
+    pub token: String,
+    /// Path of the file the section was found in.
+    pub path: Option<std::path::PathBuf>,
+}
+
+impl UnknownSection {
+    /// Set the path of the file the section was found in.
+    #[must_use]
+    pub fn with_path(mut self, path: impl AsRef<Path>) -> Self {
+        self.path = Some(path.as_ref().into());
+        self
+    }
+}
+
+impl Display for UnknownSection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.path {
+            Some(ref path) => write!(f, "{}", path.display())?,
+            None => write!(f, "<unknown>")?,
+        }
+        write!(
+            f,
+            ":{}: skipped unknown section {}",
+            self.line_num + 1,
+            self.token
+        )
+    }
+}
+
+/// Scan an enumerated iterator of lines for sections in any order, passing the lines of each
+/// section to the [`SectionSink`] it belongs to. Sections may be repeated or missing, sections
+/// without a sink are skipped and returned.
+///
+/// # Errors
+/// If any of the line reads fail or a sink fails to handle a line.
+pub fn scan(
+    lines: impl Iterator<Item = (usize, io::Result<String>)>,
+    sinks: &mut [&mut dyn SectionSink],
+) -> Result<Vec<UnknownSection>, ParseErr> {
+    scan_lines(lines, sinks)
+}
+
+/// Line of a shared buffer.
+struct SharedLine<'a>(&'a Arc<str>, Range<usize>);
+
+impl AsRef<str> for SharedLine<'_> {
+    fn as_ref(&self) -> &str {
+        &self.0[self.1.clone()]
+    }
+}
+
+impl From<SharedLine<'_>> for ContentString {
+    fn from(SharedLine(content, range): SharedLine<'_>) -> Self {
+        ContentString::from_rc_range(Arc::clone(content), range)
+    }
+}
+
+/// Scan the content of a whole file for sections like [`scan`], with all items sharing the
+/// content as their buffer instead of allocating a string per line. Lines of older format
+/// versions are upgraded using `migrations`, in which case items no longer share the content.
+///
+/// # Errors
+/// If the header is malformed or newer than supported, or if a sink fails to handle a line.
+pub fn scan_shared(
+    content: &Arc<str>,
+    migrations: &Migrations,
+    sinks: &mut [&mut dyn SectionSink],
+) -> Result<Vec<UnknownSection>, ParseErr> {
+    let version = match content.lines().next() {
+        Some(line) => read_header(line, Some(0))?.unwrap_or(0),
+        None => 0,
+    };
+
+    if version != migrations.version() {
+        let lines = content.lines().map(|line| Ok(line.to_owned())).enumerate();
+        return scan(migrations.upgrade(lines)?, sinks);
+    }
+
+    let lines = content.lines().enumerate().map(|(i, line)| {
+        let begin = line.as_ptr() as usize - content.as_ptr() as usize;
+        (i, Ok(SharedLine(content, begin..begin + line.len())))
+    });
+
+    scan_lines(lines, sinks)
+}
+
+fn scan_lines<L>(
+    lines: impl Iterator<Item = (usize, io::Result<L>)>,
+    sinks: &mut [&mut dyn SectionSink],
+) -> Result<Vec<UnknownSection>, ParseErr>
+where
+    L: AsRef<str> + Into<ContentString>,
+{
+    enum State {
+        Outside,
+        Known(usize),
+        Unknown(String),
+    }
+
+    let mut unknown = Vec::new();
+    let mut state = State::Outside;
+
+    for (i, result) in lines {
+        let line = result?;
+        let text = line.as_ref();
+
+        state = match state {
+            State::Outside => {
+                if let Some(index) = sinks.iter().position(|sink| sink.token_begin() == text) {
+                    sinks[index].begin_section();
+                    State::Known(index)
+                } else if let Some(name) = text
+                    .strip_prefix('#')
+                    .and_then(|line| line.strip_suffix("_BEGIN"))
+                {
+                    let end = format!("#{name}_END");
+                    unknown.push(UnknownSection {
+                        line_num: i,
+                        token: text.into(),
+                        path: None,
+                    });
+                    State::Unknown(end)
+                } else {
+                    State::Outside
+                }
+            }
+            State::Known(index) => {
+                if sinks[index].token_end() == text {
+                    State::Outside
+                } else {
+                    sinks[index].push_line(line.into(), i)?;
+                    State::Known(index)
+                }
+            }
+            State::Unknown(end) => {
+                if end == text {
+                    State::Outside
+                } else {
+                    State::Unknown(end)
+                }
+            }
+        };
+    }
+
+    Ok(unknown)
+}
@@ -1,19 +1,67 @@
 use crate::{ContentString, ParseErr, Property, PropertyErr};
 
+/// Least amount of bytes no longer used by any field before a [Storeable] is automatically
+/// compacted.
+pub const COMPACT_MIN_WASTE: usize = 256;
+
 /// Trait used to mark a type as serializable.
 pub trait Storeable: Sized {
+    /// Names of the properties accepted by [`get`](Storeable::get).
+    const PROPERTIES: &'static [&'static str];
+
     /// Whether or not the type has been edited.
     fn is_edited(&self) -> bool;
 
     /// Get a string from an instance.
     fn to_line(&self) -> String;
 
+    /// Amount of bytes stored by the instance that are no longer used by any field.
+    fn waste(&self) -> usize;
+
+    /// Amount of bytes stored by the instance that were content of a field but no longer are, unlike
+    /// [`waste`](Storeable::waste) bytes such as tokens are not counted.
+    fn stale(&self) -> usize;
+
+    /// Amount of bytes stored by the instance.
+    fn stored_len(&self) -> usize;
+
+    /// Rebuild the stored content such that it only contains content used by fields.
+    fn compact(&mut self);
+
+    /// Compact the instance if the bytes no longer in use exceed both [`COMPACT_MIN_WASTE`] and
+    /// the bytes in use, returns whether or not the instance was compacted.
+    fn compact_if_wasteful(&mut self) -> bool {
+        let waste = self.waste();
+        if waste > COMPACT_MIN_WASTE && waste > self.stored_len() - waste {
+            self.compact();
+            true
+        } else {
+            false
+        }
+    }
+
     /// Get a property from the instance.
     ///
     /// # Errors
     /// If the property does not exist.
     fn get(&self, property: &str) -> Result<Property, PropertyErr>;
 
+    /// Check whether or not any value of a property satisfies a predicate, unlike
+    /// [`get`](Storeable::get) values are borrowed where possible.
+    ///
+    /// # Errors
+    /// If the property does not exist.
+    fn any_value(
+        &self,
+        property: &str,
+        predicate: &mut dyn FnMut(&str) -> bool,
+    ) -> Result<bool, PropertyErr> {
+        Ok(match self.get(property)? {
+            Property::Single(value) => predicate(&value),
+            Property::List(values) => values.iter().any(|value| predicate(value)),
+        })
+    }
+
     /// Set a property on the instance.
     ///
     /// # Errors
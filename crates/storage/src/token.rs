@@ -2,3 +2,13 @@
 
 /// Default delimiter used to serialize list fields.
 pub const DELIM: &str = "<,>";
+
+/// Character starting an escape sequence in serialized field content.
+///
+/// Field content is escaped when serialized such that it never contains a `<`, meaning it can
+/// never be mistaken for a token or [`DELIM`]. The sequences used are `\\` for `\`, `\l` for `<`,
+/// `\n` for a newline and `\r` for a carriage return.
+pub const ESCAPE: char = '\\';
+
+/// Beginning of the header line stating which format version a file was written with.
+pub const VERSION: &str = "#VERSION";